@@ -0,0 +1,41 @@
+// verification-helper: PROBLEM https://judge.yosupo.jp/problem/range_affine_range_sum
+
+use ac_library::ModInt998244353 as Mint;
+use asakuchi_acl_segtree_helper::{sum_affine_tree_from, AffineMappingType, ValueAccess};
+use std::io::{Read, Write};
+
+fn main() {
+    let mut buf = String::new();
+    std::io::stdin().read_to_string(&mut buf).unwrap();
+
+    let mut iter = buf
+        .split_ascii_whitespace()
+        .map(|x| x.parse::<usize>().unwrap());
+
+    let n = iter.next().unwrap();
+    let q = iter.next().unwrap();
+
+    let a = (0..n)
+        .map(|_| Mint::new(iter.next().unwrap()))
+        .collect::<Vec<_>>();
+
+    let mut tree = sum_affine_tree_from(a);
+
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+
+    for _ in 0..q {
+        let t = iter.next().unwrap();
+        let l = iter.next().unwrap();
+        let r = iter.next().unwrap();
+
+        if t == 0 {
+            let b = Mint::new(iter.next().unwrap());
+            let c = Mint::new(iter.next().unwrap());
+
+            tree.apply_range(l..r, AffineMappingType::new(b, c));
+        } else {
+            writeln!(out, "{}", tree.prod_value(l..r)).unwrap();
+        }
+    }
+}
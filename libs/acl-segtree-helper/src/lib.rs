@@ -1,52 +1,483 @@
-use ac_library::{LazySegtree, MapMonoid, Min, Monoid};
+mod segment_tree_beats;
 
+pub use segment_tree_beats::SegmentTreeBeats;
+
+use ac_library::modint::{DynamicModInt, Id, Modulus, StaticModInt};
+use ac_library::{LazySegtree, MapMonoid, Monoid};
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, RangeBounds};
+
+///
+/// プリセットで扱う値の型
+///
+/// 整数、浮動小数点数、ac-library の ModInt に実装済み
+///
+pub trait Numeric: Copy + PartialEq + Add<Output = Self> + Mul<Output = Self> {
+    fn zero() -> Self;
+
+    fn from_usize(x: usize) -> Self;
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty),*) => {
+        $(impl Numeric for $t {
+            fn zero() -> Self {
+                0 as $t
+            }
+
+            fn from_usize(x: usize) -> Self {
+                x as $t
+            }
+        })*
+    };
+}
+impl_numeric!(i32, i64, i128, isize, u32, u64, u128, usize, f32, f64);
+
+impl<M: Modulus> Numeric for StaticModInt<M> {
+    fn zero() -> Self {
+        Self::raw(0)
+    }
+
+    fn from_usize(x: usize) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<I: Id> Numeric for DynamicModInt<I> {
+    fn zero() -> Self {
+        Self::raw(0)
+    }
+
+    fn from_usize(x: usize) -> Self {
+        Self::new(x)
+    }
+}
+
+///
+/// 最小値の単位元に使う上限・下限
+///
+pub trait Bounded {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_bounded {
+    ($($t:ty),*) => {
+        $(impl Bounded for $t {
+            fn min_value() -> Self {
+                <$t>::MIN
+            }
+
+            fn max_value() -> Self {
+                <$t>::MAX
+            }
+        })*
+    };
+}
+impl_bounded!(i32, i64, i128, isize, u32, u64, u128, usize);
+
+macro_rules! impl_bounded_float {
+    ($($t:ty),*) => {
+        $(impl Bounded for $t {
+            fn min_value() -> Self {
+                <$t>::NEG_INFINITY
+            }
+
+            fn max_value() -> Self {
+                <$t>::INFINITY
+            }
+        })*
+    };
+}
+impl_bounded_float!(f32, f64);
+
+///
+/// 区間加算・区間和
+///
+/// ```
+/// use asakuchi_acl_segtree_helper::sum_add_tree;
+///
+/// let mut tree = sum_add_tree(5);
+///
+/// tree.apply_range(1..4, 1_000_000_000);
+///
+/// assert_eq!(tree.prod(..).value, 3_000_000_000);
+/// ```
+///
 pub fn sum_add_tree(n: usize) -> LazySegtree<SumAdd> {
-    LazySegtree::<SumAdd>::from(vec![Data::one_cell(); n])
+    sum_add_tree_of(n)
 }
 
+///
+/// 区間更新・区間和
+///
 pub fn sum_update_tree(n: usize) -> LazySegtree<SumUpdate> {
-    LazySegtree::<SumUpdate>::from(vec![Data::one_cell(); n])
+    sum_update_tree_of(n)
 }
 
+///
+/// 区間加算・区間最小値
+///
 pub fn min_add_tree(n: usize) -> LazySegtree<MinAdd> {
-    LazySegtree::<MinAdd>::from(vec![0; n])
+    min_add_tree_of(n)
 }
 
+///
+/// 区間更新・区間最小値
+///
 pub fn min_update_tree(n: usize) -> LazySegtree<MinUpdate> {
-    LazySegtree::<MinUpdate>::from(vec![0; n])
+    min_update_tree_of(n)
+}
+
+///
+/// 値の型を指定して区間加算・区間和の木を作る
+///
+/// ```
+/// use asakuchi_acl_segtree_helper::sum_add_tree_of;
+///
+/// let mut tree = sum_add_tree_of::<i64>(5);
+///
+/// tree.apply_range(1..4, 3);
+///
+/// assert_eq!(tree.prod(..).value, 9);
+/// ```
+///
+pub fn sum_add_tree_of<T: Numeric>(n: usize) -> LazySegtree<SumAdd<T>> {
+    LazySegtree::<SumAdd<T>>::from(vec![Data::one_cell(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間和の木を作る
+///
+pub fn sum_update_tree_of<T: Numeric>(n: usize) -> LazySegtree<SumUpdate<T>> {
+    LazySegtree::<SumUpdate<T>>::from(vec![Data::one_cell(); n])
+}
+
+///
+/// 値の型を指定して区間加算・区間最小値の木を作る
+///
+pub fn min_add_tree_of<T: Numeric + PartialOrd + Bounded>(n: usize) -> LazySegtree<MinAdd<T>> {
+    LazySegtree::<MinAdd<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間最小値の木を作る
+///
+pub fn min_update_tree_of<T: Numeric + PartialOrd + Bounded>(
+    n: usize,
+) -> LazySegtree<MinUpdate<T>> {
+    LazySegtree::<MinUpdate<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 区間加算・区間最大値
+///
+pub fn max_add_tree(n: usize) -> LazySegtree<MaxAdd> {
+    max_add_tree_of(n)
+}
+
+///
+/// 区間更新・区間最大値
+///
+pub fn max_update_tree(n: usize) -> LazySegtree<MaxUpdate> {
+    max_update_tree_of(n)
+}
+
+///
+/// 区間更新・区間加算・区間和
+///
+/// ```
+/// use asakuchi_acl_segtree_helper::{sum_add_update_tree, AddUpdateMappingType, ValueAccess};
+///
+/// let mut tree = sum_add_update_tree(5);
+///
+/// tree.apply_range(.., AddUpdateMappingType::add(2));
+/// tree.apply_range(1..3, AddUpdateMappingType::update(10));
+/// tree.apply_range(2.., AddUpdateMappingType::add(-1));
+///
+/// assert_eq!(tree.values(), vec![2, 10, 9, 1, 1]);
+/// ```
+///
+pub fn sum_add_update_tree(n: usize) -> LazySegtree<SumAddUpdate> {
+    sum_add_update_tree_of(n)
+}
+
+///
+/// 区間更新・区間加算・区間最小値
+///
+pub fn min_add_update_tree(n: usize) -> LazySegtree<MinAddUpdate> {
+    min_add_update_tree_of(n)
+}
+
+///
+/// 区間更新・区間加算・区間最大値
+///
+pub fn max_add_update_tree(n: usize) -> LazySegtree<MaxAddUpdate> {
+    max_add_update_tree_of(n)
+}
+
+///
+/// 値の型を指定して区間加算・区間最大値の木を作る
+///
+pub fn max_add_tree_of<T: Numeric + PartialOrd + Bounded>(n: usize) -> LazySegtree<MaxAdd<T>> {
+    LazySegtree::<MaxAdd<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間最大値の木を作る
+///
+pub fn max_update_tree_of<T: Numeric + PartialOrd + Bounded>(
+    n: usize,
+) -> LazySegtree<MaxUpdate<T>> {
+    LazySegtree::<MaxUpdate<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間加算・区間和の木を作る
+///
+pub fn sum_add_update_tree_of<T: Numeric>(n: usize) -> LazySegtree<SumAddUpdate<T>> {
+    LazySegtree::<SumAddUpdate<T>>::from(vec![Data::one_cell(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間加算・区間最小値の木を作る
+///
+pub fn min_add_update_tree_of<T: Numeric + PartialOrd + Bounded>(
+    n: usize,
+) -> LazySegtree<MinAddUpdate<T>> {
+    LazySegtree::<MinAddUpdate<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 値の型を指定して区間更新・区間加算・区間最大値の木を作る
+///
+pub fn max_add_update_tree_of<T: Numeric + PartialOrd + Bounded>(
+    n: usize,
+) -> LazySegtree<MaxAddUpdate<T>> {
+    LazySegtree::<MaxAddUpdate<T>>::from(vec![T::zero(); n])
+}
+
+///
+/// 区間アフィン変換・区間和
+///
+/// ```
+/// use ac_library::ModInt998244353 as Mint;
+/// use asakuchi_acl_segtree_helper::{sum_affine_tree_from, AffineMappingType, ValueAccess};
+///
+/// let mut tree = sum_affine_tree_from(vec![Mint::new(1), Mint::new(2), Mint::new(3)]);
+///
+/// // x -> 2x + 1
+/// tree.apply_range(1.., AffineMappingType::new(Mint::new(2), Mint::new(1)));
+///
+/// assert_eq!(tree.prod_value(..), Mint::new(13));
+/// ```
+///
+pub fn sum_affine_tree(n: usize) -> LazySegtree<SumAffine> {
+    sum_affine_tree_of(n)
+}
+
+///
+/// 値の型を指定して区間アフィン変換・区間和の木を作る
+///
+pub fn sum_affine_tree_of<T: Numeric>(n: usize) -> LazySegtree<SumAffine<T>> {
+    LazySegtree::<SumAffine<T>>::from(vec![Data::one_cell(); n])
+}
+
+///
+/// 初期値から区間加算・区間和の木を作る
+///
+/// ```
+/// use asakuchi_acl_segtree_helper::{sum_add_tree_from, ValueAccess};
+///
+/// let mut tree = sum_add_tree_from(vec![3_i64, 1, 4, 1, 5]);
+///
+/// tree.apply_range(..2, 10);
+///
+/// assert_eq!(tree.prod_value(1..3), 15);
+/// assert_eq!(tree.values(), vec![13, 11, 4, 1, 5]);
+/// ```
+///
+pub fn sum_add_tree_from<T: Numeric>(values: Vec<T>) -> LazySegtree<SumAdd<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間和の木を作る
+///
+pub fn sum_update_tree_from<T: Numeric>(values: Vec<T>) -> LazySegtree<SumUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間加算・区間最小値の木を作る
+///
+pub fn min_add_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MinAdd<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間最小値の木を作る
+///
+pub fn min_update_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MinUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間加算・区間最大値の木を作る
+///
+pub fn max_add_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MaxAdd<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間最大値の木を作る
+///
+pub fn max_update_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MaxUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間加算・区間和の木を作る
+///
+pub fn sum_add_update_tree_from<T: Numeric>(values: Vec<T>) -> LazySegtree<SumAddUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間加算・区間最小値の木を作る
+///
+pub fn min_add_update_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MinAddUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間更新・区間加算・区間最大値の木を作る
+///
+pub fn max_add_update_tree_from<T: Numeric + PartialOrd + Bounded>(
+    values: Vec<T>,
+) -> LazySegtree<MaxAddUpdate<T>> {
+    build(values)
+}
+
+///
+/// 初期値から区間アフィン変換・区間和の木を作る
+///
+pub fn sum_affine_tree_from<T: Numeric>(values: Vec<T>) -> LazySegtree<SumAffine<T>> {
+    build(values)
+}
+
+fn build<F: Preset>(values: Vec<F::Value>) -> LazySegtree<F> {
+    LazySegtree::<F>::from(values.into_iter().map(F::to_data).collect::<Vec<_>>())
+}
+
+///
+/// プリセットの値とセグメント木が持つデータとの変換
+///
+pub trait Preset: MapMonoid {
+    ///
+    /// 値の型
+    ///
+    type Value;
+
+    ///
+    /// 1 要素分の値をデータに変換する
+    ///
+    fn to_data(value: Self::Value) -> <Self::M as Monoid>::S;
+
+    ///
+    /// データから値を取り出す
+    ///
+    fn from_data(data: &<Self::M as Monoid>::S) -> Self::Value;
+}
+
+///
+/// `Data` や `size` を意識せずに値を読み書きする
+///
+pub trait ValueAccess {
+    type Value;
+
+    fn get_value(&mut self, p: usize) -> Self::Value;
+
+    fn set_value(&mut self, p: usize, value: Self::Value);
+
+    fn prod_value<R: RangeBounds<usize>>(&mut self, range: R) -> Self::Value;
+
+    ///
+    /// 全ての位置の値
+    ///
+    fn values(&mut self) -> Vec<Self::Value>;
+}
+
+impl<F: Preset> ValueAccess for LazySegtree<F> {
+    type Value = F::Value;
+
+    fn get_value(&mut self, p: usize) -> Self::Value {
+        F::from_data(&self.get(p))
+    }
+
+    fn set_value(&mut self, p: usize, value: Self::Value) {
+        self.set(p, F::to_data(value));
+    }
+
+    fn prod_value<R: RangeBounds<usize>>(&mut self, range: R) -> Self::Value {
+        F::from_data(&self.prod(range))
+    }
+
+    fn values(&mut self) -> Vec<Self::Value> {
+        // LazySegtree は要素数を公開していないので max_right で求める
+        let n = self.max_right(0, |_| true);
+
+        (0..n).map(|p| self.get_value(p)).collect()
+    }
 }
 
 #[derive(Clone)]
-pub struct Data {
-    pub value: isize,
+pub struct Data<T = isize> {
+    pub value: T,
     pub size: usize,
 }
 
-impl Data {
-    pub fn one_cell() -> Data {
-        Data { value: 0, size: 1 }
+impl<T: Numeric> Data<T> {
+    pub fn one_cell() -> Data<T> {
+        Data {
+            value: T::zero(),
+            size: 1,
+        }
     }
 }
 
 #[derive(Clone)]
-pub enum RangeUpdateMappingType {
-    Value(isize),
+pub enum RangeUpdateMappingType<T = isize> {
+    Value(T),
     ID,
 }
 
-pub struct RangeSumMonoid;
+pub struct RangeSumMonoid<T = isize>(PhantomData<T>);
 
-impl Monoid for RangeSumMonoid {
+impl<T: Numeric> Monoid for RangeSumMonoid<T> {
     ///
     /// モノイドの型
     ///
-    type S = Data;
+    type S = Data<T>;
 
     ///
     /// 単位元
     ///
     fn identity() -> Self::S {
-        Data { value: 0, size: 0 }
+        Data {
+            value: T::zero(),
+            size: 0,
+        }
     }
 
     ///
@@ -60,19 +491,81 @@ impl Monoid for RangeSumMonoid {
     }
 }
 
-pub struct SumAdd;
+///
+/// 最小値
+///
+/// `ac_library::Min` と違い、浮動小数点数にも使える
+///
+pub struct RangeMinMonoid<T = isize>(PhantomData<T>);
 
-impl MapMonoid for SumAdd {
-    type M = RangeSumMonoid;
+impl<T: Copy + PartialOrd + Bounded> Monoid for RangeMinMonoid<T> {
+    ///
+    /// モノイドの型
+    ///
+    type S = T;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S {
+        T::max_value()
+    }
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(&a: &Self::S, &b: &Self::S) -> Self::S {
+        if a <= b {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+///
+/// 最大値
+///
+pub struct RangeMaxMonoid<T = isize>(PhantomData<T>);
+
+impl<T: Copy + PartialOrd + Bounded> Monoid for RangeMaxMonoid<T> {
+    ///
+    /// モノイドの型
+    ///
+    type S = T;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S {
+        T::min_value()
+    }
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(&a: &Self::S, &b: &Self::S) -> Self::S {
+        if a >= b {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+pub struct SumAdd<T = isize>(PhantomData<T>);
+
+impl<T: Numeric> MapMonoid for SumAdd<T> {
+    type M = RangeSumMonoid<T>;
     /// 写像の型
-    type F = isize;
+    type F = T;
 
     ///
     /// 恒等写像
     /// 全ての`a`に対して`mapping(id, a) = a`となるもの
     ///
     fn identity_map() -> Self::F {
-        0
+        T::zero()
     }
 
     ///
@@ -82,7 +575,7 @@ impl MapMonoid for SumAdd {
     ///
     fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
         Data {
-            value: x.value + x.size as isize * f,
+            value: x.value + T::from_usize(x.size) * *f,
             size: x.size,
         }
     }
@@ -98,12 +591,12 @@ impl MapMonoid for SumAdd {
     }
 }
 
-pub struct SumUpdate;
+pub struct SumUpdate<T = isize>(PhantomData<T>);
 
-impl MapMonoid for SumUpdate {
-    type M = RangeSumMonoid;
+impl<T: Numeric> MapMonoid for SumUpdate<T> {
+    type M = RangeSumMonoid<T>;
     /// 写像の型
-    type F = RangeUpdateMappingType;
+    type F = RangeUpdateMappingType<T>;
 
     ///
     /// 恒等写像
@@ -121,7 +614,7 @@ impl MapMonoid for SumUpdate {
     fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
         if let RangeUpdateMappingType::Value(value) = f {
             Data {
-                value: x.size as isize * value,
+                value: T::from_usize(x.size) * *value,
                 size: x.size,
             }
         } else {
@@ -147,19 +640,19 @@ impl MapMonoid for SumUpdate {
     }
 }
 
-pub struct MinAdd;
+pub struct MinAdd<T = isize>(PhantomData<T>);
 
-impl MapMonoid for MinAdd {
-    type M = Min<isize>;
+impl<T: Numeric + PartialOrd + Bounded> MapMonoid for MinAdd<T> {
+    type M = RangeMinMonoid<T>;
     /// 写像の型
-    type F = isize;
+    type F = T;
 
     ///
     /// 恒等写像
     /// 全ての`a`に対して`mapping(id, a) = a`となるもの
     ///
     fn identity_map() -> Self::F {
-        0
+        T::zero()
     }
 
     ///
@@ -182,23 +675,65 @@ impl MapMonoid for MinAdd {
     }
 }
 
-pub struct MinUpdate;
+pub struct MinUpdate<T = isize>(PhantomData<T>);
+
+impl<T: Numeric + PartialOrd + Bounded> MapMonoid for MinUpdate<T> {
+    type M = RangeMinMonoid<T>;
+    /// 写像の型
+    type F = RangeUpdateMappingType<T>;
+
+    ///
+    /// 恒等写像
+    /// 全ての`a`に対して`mapping(id, a) = a`となるもの
+    ///
+    fn identity_map() -> Self::F {
+        RangeUpdateMappingType::ID
+    }
 
-impl MinUpdate {
-    const ID: isize = isize::MAX;
+    ///
+    /// f(x) を返す関数
+    ///
+    /// dataの値`x`に対して作用させる関数
+    ///
+    fn mapping(f: &Self::F, &x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
+        if let RangeUpdateMappingType::Value(value) = f {
+            *value
+        } else {
+            // f が ID ならそのまま x を返す
+            x
+        }
+    }
+
+    ///
+    /// f∘g を返す関数
+    ///
+    /// `g` がこれまでの操作、`f` が後に追加する操作で、
+    ///「その2つの操作を順に行うようなひとまとめの操作（合成写像）」を返す
+    ///
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        if let RangeUpdateMappingType::Value(_) = f {
+            // 後からの操作で上書き
+            f.clone()
+        } else {
+            // f が ID ならそのまま g を返す
+            g.clone()
+        }
+    }
 }
 
-impl MapMonoid for MinUpdate {
-    type M = Min<isize>;
+pub struct MaxAdd<T = isize>(PhantomData<T>);
+
+impl<T: Numeric + PartialOrd + Bounded> MapMonoid for MaxAdd<T> {
+    type M = RangeMaxMonoid<T>;
     /// 写像の型
-    type F = isize;
+    type F = T;
 
     ///
     /// 恒等写像
     /// 全ての`a`に対して`mapping(id, a) = a`となるもの
     ///
     fn identity_map() -> Self::F {
-        MinUpdate::ID
+        T::zero()
     }
 
     ///
@@ -207,9 +742,45 @@ impl MapMonoid for MinUpdate {
     /// dataの値`x`に対して作用させる関数
     ///
     fn mapping(&f: &Self::F, &x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
-        if f != MinUpdate::ID {
-            f
+        f + x
+    }
+
+    ///
+    /// f∘g を返す関数
+    ///
+    /// `g` がこれまでの操作、`f` が後に追加する操作で、
+    ///「その2つの操作を順に行うようなひとまとめの操作（合成写像）」を返す
+    ///
+    fn composition(&f: &Self::F, &g: &Self::F) -> Self::F {
+        f + g
+    }
+}
+
+pub struct MaxUpdate<T = isize>(PhantomData<T>);
+
+impl<T: Numeric + PartialOrd + Bounded> MapMonoid for MaxUpdate<T> {
+    type M = RangeMaxMonoid<T>;
+    /// 写像の型
+    type F = RangeUpdateMappingType<T>;
+
+    ///
+    /// 恒等写像
+    /// 全ての`a`に対して`mapping(id, a) = a`となるもの
+    ///
+    fn identity_map() -> Self::F {
+        RangeUpdateMappingType::ID
+    }
+
+    ///
+    /// f(x) を返す関数
+    ///
+    /// dataの値`x`に対して作用させる関数
+    ///
+    fn mapping(f: &Self::F, &x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
+        if let RangeUpdateMappingType::Value(value) = f {
+            *value
         } else {
+            // f が ID ならそのまま x を返す
             x
         }
     }
@@ -220,18 +791,598 @@ impl MapMonoid for MinUpdate {
     /// `g` がこれまでの操作、`f` が後に追加する操作で、
     ///「その2つの操作を順に行うようなひとまとめの操作（合成写像）」を返す
     ///
-    fn composition(&f: &Self::F, &g: &Self::F) -> Self::F {
-        if f == MinUpdate::ID {
-            g
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        if let RangeUpdateMappingType::Value(_) = f {
+            // 後からの操作で上書き
+            f.clone()
         } else {
-            f
+            // f が ID ならそのまま g を返す
+            g.clone()
         }
     }
 }
 
+///
+/// 区間更新と区間加算をまとめた写像
+///
+/// x を `update`（None なら x のまま）にしてから `add` を加える
+///
+#[derive(Clone)]
+pub struct AddUpdateMappingType<T = isize> {
+    pub update: Option<T>,
+    pub add: T,
+}
+
+impl<T: Numeric> AddUpdateMappingType<T> {
+    ///
+    /// 区間に x を加える写像
+    ///
+    pub fn add(x: T) -> AddUpdateMappingType<T> {
+        AddUpdateMappingType {
+            update: None,
+            add: x,
+        }
+    }
+
+    ///
+    /// 区間を x にする写像
+    ///
+    pub fn update(x: T) -> AddUpdateMappingType<T> {
+        AddUpdateMappingType {
+            update: Some(x),
+            add: T::zero(),
+        }
+    }
+}
+
+///
+/// [`AddUpdate`] を作用させられるモノイド
+///
+pub trait AddUpdateMonoid: Monoid {
+    type T: Numeric;
+
+    fn apply(f: &AddUpdateMappingType<Self::T>, x: &Self::S) -> Self::S;
+}
+
+impl<T: Numeric> AddUpdateMonoid for RangeSumMonoid<T> {
+    type T = T;
+
+    fn apply(f: &AddUpdateMappingType<T>, x: &Data<T>) -> Data<T> {
+        let size = T::from_usize(x.size);
+
+        let value = match f.update {
+            Some(update) => update * size,
+            None => x.value,
+        };
+
+        Data {
+            value: value + f.add * size,
+            size: x.size,
+        }
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> AddUpdateMonoid for RangeMinMonoid<T> {
+    type T = T;
+
+    fn apply(f: &AddUpdateMappingType<T>, &x: &T) -> T {
+        f.update.unwrap_or(x) + f.add
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> AddUpdateMonoid for RangeMaxMonoid<T> {
+    type T = T;
+
+    fn apply(f: &AddUpdateMappingType<T>, &x: &T) -> T {
+        f.update.unwrap_or(x) + f.add
+    }
+}
+
+///
+/// 区間更新・区間加算
+///
+/// 更新と加算をどの順に作用させてもよい
+///
+pub struct AddUpdate<M>(PhantomData<M>);
+
+pub type SumAddUpdate<T = isize> = AddUpdate<RangeSumMonoid<T>>;
+pub type MinAddUpdate<T = isize> = AddUpdate<RangeMinMonoid<T>>;
+pub type MaxAddUpdate<T = isize> = AddUpdate<RangeMaxMonoid<T>>;
+
+impl<M: AddUpdateMonoid> MapMonoid for AddUpdate<M> {
+    type M = M;
+    /// 写像の型
+    type F = AddUpdateMappingType<M::T>;
+
+    ///
+    /// 恒等写像
+    /// 全ての`a`に対して`mapping(id, a) = a`となるもの
+    ///
+    fn identity_map() -> Self::F {
+        AddUpdateMappingType::add(M::T::zero())
+    }
+
+    ///
+    /// f(x) を返す関数
+    ///
+    /// dataの値`x`に対して作用させる関数
+    ///
+    fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
+        M::apply(f, x)
+    }
+
+    ///
+    /// f∘g を返す関数
+    ///
+    /// `g` がこれまでの操作、`f` が後に追加する操作で、
+    ///「その2つの操作を順に行うようなひとまとめの操作（合成写像）」を返す
+    ///
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        if f.update.is_some() {
+            // 後からの更新で上書き
+            f.clone()
+        } else {
+            AddUpdateMappingType {
+                update: g.update,
+                add: g.add + f.add,
+            }
+        }
+    }
+}
+
+///
+/// アフィン変換 x -> a * x + b
+///
+#[derive(Clone)]
+pub struct AffineMappingType<T = isize> {
+    pub a: T,
+    pub b: T,
+}
+
+impl<T: Numeric> AffineMappingType<T> {
+    pub fn new(a: T, b: T) -> AffineMappingType<T> {
+        AffineMappingType { a, b }
+    }
+}
+
+///
+/// 区間アフィン変換・区間和
+///
+/// ModInt を使えば法 p の下で計算できる
+///
+pub struct SumAffine<T = isize>(PhantomData<T>);
+
+impl<T: Numeric> MapMonoid for SumAffine<T> {
+    type M = RangeSumMonoid<T>;
+    /// 写像の型
+    type F = AffineMappingType<T>;
+
+    ///
+    /// 恒等写像
+    /// 全ての`a`に対して`mapping(id, a) = a`となるもの
+    ///
+    fn identity_map() -> Self::F {
+        AffineMappingType::new(T::from_usize(1), T::zero())
+    }
+
+    ///
+    /// f(x) を返す関数
+    ///
+    /// dataの値`x`に対して作用させる関数
+    ///
+    fn mapping(f: &Self::F, x: &<Self::M as Monoid>::S) -> <Self::M as Monoid>::S {
+        Data {
+            value: f.a * x.value + f.b * T::from_usize(x.size),
+            size: x.size,
+        }
+    }
+
+    ///
+    /// f∘g を返す関数
+    ///
+    /// `g` がこれまでの操作、`f` が後に追加する操作で、
+    ///「その2つの操作を順に行うようなひとまとめの操作（合成写像）」を返す
+    ///
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        // f(g(x)) = f.a * (g.a * x + g.b) + f.b
+        AffineMappingType::new(f.a * g.a, f.a * g.b + f.b)
+    }
+}
+
+impl<T: Numeric> Preset for SumAdd<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> Data<T> {
+        Data { value, size: 1 }
+    }
+
+    fn from_data(data: &Data<T>) -> T {
+        data.value
+    }
+}
+
+impl<T: Numeric> Preset for SumUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> Data<T> {
+        Data { value, size: 1 }
+    }
+
+    fn from_data(data: &Data<T>) -> T {
+        data.value
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MinAdd<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MinUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MaxAdd<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MaxUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric> Preset for SumAddUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> Data<T> {
+        Data { value, size: 1 }
+    }
+
+    fn from_data(data: &Data<T>) -> T {
+        data.value
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MinAddUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric + PartialOrd + Bounded> Preset for MaxAddUpdate<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> T {
+        value
+    }
+
+    fn from_data(&data: &T) -> T {
+        data
+    }
+}
+
+impl<T: Numeric> Preset for SumAffine<T> {
+    type Value = T;
+
+    fn to_data(value: T) -> Data<T> {
+        Data { value, size: 1 }
+    }
+
+    fn from_data(data: &Data<T>) -> T {
+        data.value
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    // use super::UnionFind;
+    use super::*;
+    use ac_library::ModInt998244353 as Mint;
+
+    #[test]
+    fn test_sum_presets() {
+        let mut tree = sum_add_tree_of::<i64>(6);
+
+        tree.apply_range(1..5, 1_000_000_000_000);
+        tree.apply_range(3.., -2);
+
+        assert_eq!(tree.prod(..).value, 4_000_000_000_000 - 6);
+        assert_eq!(tree.prod(4..6).value, 1_000_000_000_000 - 4);
+
+        let mut tree = sum_add_tree_of::<Mint>(4);
+
+        tree.apply_range(.., Mint::new(998_244_352));
+        tree.apply_range(2..3, Mint::new(5));
+
+        assert_eq!(tree.prod(..).value, Mint::new(1));
+
+        let mut tree = sum_update_tree_of::<f64>(5);
+
+        tree.apply_range(.., RangeUpdateMappingType::Value(1.5));
+        tree.apply_range(1..3, RangeUpdateMappingType::Value(-0.25));
+
+        assert_eq!(tree.prod(..).value, 4.0);
+
+        // 型を指定しなければ isize
+        let mut tree = sum_add_tree(3);
+        tree.apply_range(.., 1_000_000_000);
+        tree.set(1, Data { value: 1, size: 1 });
+
+        assert_eq!(tree.prod(..).value, 2_000_000_001);
+
+        let mut tree = sum_update_tree(3);
+        tree.apply_range(1.., RangeUpdateMappingType::Value(2_000_000_000));
+
+        assert_eq!(tree.prod(..).value, 4_000_000_000);
+    }
+
+    #[test]
+    fn test_min_presets() {
+        let mut tree = min_add_tree_of::<u64>(5);
+
+        tree.apply_range(.., 10);
+        tree.apply_range(1..3, 5);
+
+        assert_eq!(tree.prod(1..4), 10);
+        assert_eq!(tree.prod(1..3), 15);
+
+        let mut tree = min_update_tree_of::<f64>(4);
+
+        tree.apply_range(..2, RangeUpdateMappingType::Value(-1.5));
+        tree.apply_range(1.., RangeUpdateMappingType::Value(2.5));
+
+        assert_eq!(tree.prod(..), -1.5);
+        assert_eq!(tree.prod(1..), 2.5);
+
+        let mut tree = min_update_tree_of::<i128>(3);
+
+        tree.apply(2, RangeUpdateMappingType::Value(-(1 << 100)));
+
+        assert_eq!(tree.prod(..), -(1 << 100));
+
+        // 型の最大値にも更新できる
+        let mut tree = min_update_tree_from(vec![5_u64, 7]);
+
+        tree.apply_range(1.., RangeUpdateMappingType::Value(u64::MAX));
+
+        assert_eq!(tree.values(), vec![5, u64::MAX]);
+
+        let mut tree = min_add_tree(2);
+        tree.apply_range(.., 3_000_000_000);
+
+        assert_eq!(tree.prod(..), 3_000_000_000);
+
+        let mut tree = min_update_tree(2);
+        tree.apply_range(..1, RangeUpdateMappingType::Value(-4));
+
+        assert_eq!(tree.prod(..), -4);
+    }
+
+    #[test]
+    fn test_from_values() {
+        let list = vec![5_i64, 3, 8, 6, 7];
+
+        let mut tree = min_add_tree_from(list.clone());
+
+        assert_eq!(tree.prod_value(..), 3);
+        assert_eq!(tree.values(), list);
+
+        tree.apply_range(1..2, 10);
+        tree.set_value(4, 2);
+
+        assert_eq!(tree.prod_value(..), 2);
+        assert_eq!(tree.prod_value(..4), 5);
+        assert_eq!(tree.get_value(1), 13);
+
+        let mut tree = min_update_tree_from(list.clone());
+
+        tree.apply_range(2.., RangeUpdateMappingType::Value(4));
+
+        assert_eq!(tree.values(), vec![5, 3, 4, 4, 4]);
+
+        let mut tree = sum_update_tree_from(list.clone());
+
+        tree.apply_range(..2, RangeUpdateMappingType::Value(1));
+
+        assert_eq!(tree.prod_value(..), 23);
+        assert_eq!(tree.prod_value(1..3), 9);
+
+        let mut tree = sum_add_tree_from(Vec::<Mint>::new());
+
+        assert!(tree.values().is_empty());
+        assert_eq!(tree.prod_value(..), Mint::new(0));
+    }
+
+    #[test]
+    fn test_max_presets() {
+        let mut tree = max_add_tree_of::<i32>(4);
+
+        tree.apply_range(1..3, 5);
+        tree.apply_range(2.., -7);
+
+        assert_eq!(tree.values(), vec![0, 5, -2, -7]);
+        assert_eq!(tree.prod_value(2..), -2);
+
+        let mut tree = max_update_tree_from(vec![1.5, -2.0, 0.25]);
+
+        tree.apply_range(..2, RangeUpdateMappingType::Value(-3.0));
+
+        assert_eq!(tree.prod_value(..), 0.25);
+        assert_eq!(tree.prod_value(..2), -3.0);
+
+        // 型の最小値にも更新できる
+        let mut tree = max_update_tree_from(vec![5_u64, 7]);
+
+        tree.apply_range(.., RangeUpdateMappingType::Value(0));
+
+        assert_eq!(tree.values(), vec![0, 0]);
+
+        // 型を指定しなければ isize
+        let mut tree = max_add_tree(2);
+        tree.apply_range(1.., 3_000_000_000);
+
+        assert_eq!(tree.prod(..), 3_000_000_000);
+
+        let mut tree = max_update_tree(2);
+        tree.apply_range(..1, RangeUpdateMappingType::Value(-4));
+
+        assert_eq!(tree.prod(..), 0);
+    }
+
+    #[test]
+    fn test_add_update_presets() {
+        let mut list = vec![5_i64, -3, 8, 0, 2, -7, 4];
+
+        let mut sum_tree = sum_add_update_tree_from(list.clone());
+        let mut min_tree = min_add_update_tree_from(list.clone());
+        let mut max_tree = max_add_update_tree_from(list.clone());
+
+        for &(l, r, update, x) in [
+            (0, 7, false, 3),
+            (2, 5, true, -1),
+            (1, 4, false, -4),
+            (4, 7, true, 6),
+            (0, 2, true, 0),
+            (3, 3, true, 9),
+            (1, 6, false, 2),
+            (5, 7, false, -10),
+        ]
+        .iter()
+        {
+            let f = if update {
+                list[l..r].iter_mut().for_each(|value| *value = x);
+
+                AddUpdateMappingType::update(x)
+            } else {
+                list[l..r].iter_mut().for_each(|value| *value += x);
+
+                AddUpdateMappingType::add(x)
+            };
+
+            sum_tree.apply_range(l..r, f.clone());
+            min_tree.apply_range(l..r, f.clone());
+            max_tree.apply_range(l..r, f);
+
+            for l in 0..=list.len() {
+                for r in l..=list.len() {
+                    assert_eq!(sum_tree.prod_value(l..r), list[l..r].iter().sum::<i64>());
+                    assert_eq!(
+                        min_tree.prod_value(l..r),
+                        list[l..r].iter().copied().min().unwrap_or(i64::MAX)
+                    );
+                    assert_eq!(
+                        max_tree.prod_value(l..r),
+                        list[l..r].iter().copied().max().unwrap_or(i64::MIN)
+                    );
+                }
+            }
+        }
+
+        assert_eq!(sum_tree.values(), list);
+
+        // 型を指定しなければ isize
+        let mut tree = sum_add_update_tree(3);
+        tree.apply_range(.., AddUpdateMappingType::update(1_000_000_000));
+        tree.apply_range(1.., AddUpdateMappingType::add(1));
+
+        assert_eq!(tree.prod(..).value, 3_000_000_002);
+
+        let mut tree = min_add_update_tree_of::<u64>(3);
+        tree.apply_range(1.., AddUpdateMappingType::add(5));
+
+        assert_eq!(tree.prod(..), 0);
+
+        let mut tree = max_add_update_tree(3);
+        tree.apply_range(..2, AddUpdateMappingType::update(-5));
+
+        assert_eq!(tree.prod(..), 0);
+        assert_eq!(min_add_update_tree(2).prod(..), 0);
+        assert_eq!(max_add_update_tree_of::<f64>(2).prod(..), 0.0);
+    }
+
+    #[test]
+    fn test_sum_affine_presets() {
+        let mut list = (0..6)
+            .map(|i| Mint::new(i * 100_000_007))
+            .collect::<Vec<_>>();
+
+        let mut tree = sum_affine_tree_from(list.clone());
+
+        for &(l, r, a, b) in [
+            (0, 6, 2, 3),
+            (1, 4, 998_244_352, 7),
+            (2, 6, 0, 100_000_000),
+            (3, 3, 5, 5),
+            (0, 3, 123_456_789, 987_654_321),
+        ]
+        .iter()
+        {
+            let (a, b) = (Mint::new(a), Mint::new(b));
+
+            tree.apply_range(l..r, AffineMappingType::new(a, b));
+
+            for value in list[l..r].iter_mut() {
+                *value = a * *value + b;
+            }
+
+            for l in 0..=list.len() {
+                for r in l..=list.len() {
+                    assert_eq!(tree.prod_value(l..r), list[l..r].iter().sum::<Mint>());
+                }
+            }
+        }
+
+        assert_eq!(tree.values(), list);
+
+        let mut tree = sum_affine_tree_of::<i64>(3);
+
+        tree.apply_range(.., AffineMappingType::new(3, 1));
+        tree.apply_range(..2, AffineMappingType::new(-2, 5));
+
+        assert_eq!(tree.values(), vec![3, 3, 1]);
+
+        // 型を指定しなければ isize
+        let mut tree = sum_affine_tree(2);
+
+        tree.apply_range(.., AffineMappingType::new(0, 2_000_000_000));
+
+        assert_eq!(tree.prod_value(..), 4_000_000_000);
+    }
 
     #[test]
     fn test_disjoint_set() {
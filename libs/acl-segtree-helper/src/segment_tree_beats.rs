@@ -0,0 +1,578 @@
+use crate::{Bounded, Numeric};
+use std::ops::{Bound, RangeBounds, Sub};
+
+///
+/// Segment Tree Beats
+///
+/// 区間 chmin・chmax・加算・更新と、区間和・最小値・最大値の取得をならし O(log² n) で行う
+///
+/// 参考：<https://codeforces.com/blog/entry/57319>
+///
+/// ```
+/// use asakuchi_acl_segtree_helper::SegmentTreeBeats;
+///
+/// let mut tree = SegmentTreeBeats::from(vec![5_i64, 1, 8, 3, 9]);
+///
+/// tree.chmin(.., 6);
+/// assert_eq!(tree.sum(..), 5 + 1 + 6 + 3 + 6);
+///
+/// tree.chmax(1..4, 4);
+/// tree.add(..2, 10);
+/// assert_eq!(tree.min(..), 4);
+/// assert_eq!(tree.max(..), 15);
+///
+/// tree.assign(3.., 0);
+/// assert_eq!(tree.sum(2..), 6);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct SegmentTreeBeats<T> {
+    n: usize,
+    size: usize,
+    max: Vec<T>,
+    /// 2 番目に大きい値（全て同じ値なら `None`）
+    second_max: Vec<Option<T>>,
+    max_count: Vec<usize>,
+    min: Vec<T>,
+    /// 2 番目に小さい値（全て同じ値なら `None`）
+    second_min: Vec<Option<T>>,
+    min_count: Vec<usize>,
+    sum: Vec<T>,
+    /// 要素数（範囲外の葉だけなら 0）
+    len: Vec<usize>,
+    /// 子に伝播していない加算
+    lazy: Vec<T>,
+}
+
+impl<T> SegmentTreeBeats<T>
+where
+    T: Numeric + PartialOrd + Bounded + Sub<Output = T>,
+{
+    pub fn new(n: usize) -> SegmentTreeBeats<T> {
+        Self::from(vec![T::zero(); n])
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    ///
+    /// 区間の各値 a を min(a, x) にする
+    ///
+    pub fn chmin<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = self.to_half_open(range);
+
+        self.chmin_inner(1, 0, self.size, l, r, x);
+    }
+
+    ///
+    /// 区間の各値 a を max(a, x) にする
+    ///
+    pub fn chmax<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = self.to_half_open(range);
+
+        self.chmax_inner(1, 0, self.size, l, r, x);
+    }
+
+    ///
+    /// 区間に x を加える
+    ///
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = self.to_half_open(range);
+
+        self.add_inner(1, 0, self.size, l, r, x);
+    }
+
+    ///
+    /// 区間を x にする
+    ///
+    pub fn assign<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = self.to_half_open(range);
+
+        self.chmin_inner(1, 0, self.size, l, r, x);
+        self.chmax_inner(1, 0, self.size, l, r, x);
+    }
+
+    pub fn sum<R: RangeBounds<usize>>(&mut self, range: R) -> T {
+        let (l, r) = self.to_half_open(range);
+
+        self.sum_inner(1, 0, self.size, l, r)
+    }
+
+    ///
+    /// 区間の最小値（空なら `T::max_value()`）
+    ///
+    pub fn min<R: RangeBounds<usize>>(&mut self, range: R) -> T {
+        let (l, r) = self.to_half_open(range);
+
+        self.min_inner(1, 0, self.size, l, r)
+    }
+
+    ///
+    /// 区間の最大値（空なら `T::min_value()`）
+    ///
+    pub fn max<R: RangeBounds<usize>>(&mut self, range: R) -> T {
+        let (l, r) = self.to_half_open(range);
+
+        self.max_inner(1, 0, self.size, l, r)
+    }
+
+    pub fn get(&mut self, i: usize) -> T {
+        assert!(i < self.n, "index out of bounds");
+
+        self.sum(i..=i)
+    }
+
+    fn chmin_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize, x: T) {
+        if b <= l || r <= a || self.max[k] <= x {
+            return;
+        }
+
+        // 葉は 2 番目の値を持たないので、ここで必ず止まる
+        if l <= a && b <= r && self.second_max[k].map_or(true, |second| second < x) {
+            self.apply_chmin(k, x);
+            return;
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+        self.chmin_inner(2 * k, a, m, l, r, x);
+        self.chmin_inner(2 * k + 1, m, b, l, r, x);
+
+        self.update(k);
+    }
+
+    fn chmax_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize, x: T) {
+        if b <= l || r <= a || self.min[k] >= x {
+            return;
+        }
+
+        if l <= a && b <= r && self.second_min[k].map_or(true, |second| second > x) {
+            self.apply_chmax(k, x);
+            return;
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+        self.chmax_inner(2 * k, a, m, l, r, x);
+        self.chmax_inner(2 * k + 1, m, b, l, r, x);
+
+        self.update(k);
+    }
+
+    fn add_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize, x: T) {
+        if b <= l || r <= a {
+            return;
+        }
+
+        if l <= a && b <= r {
+            self.apply_add(k, x);
+            return;
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+        self.add_inner(2 * k, a, m, l, r, x);
+        self.add_inner(2 * k + 1, m, b, l, r, x);
+
+        self.update(k);
+    }
+
+    fn sum_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize) -> T {
+        if b <= l || r <= a {
+            return T::zero();
+        }
+
+        if l <= a && b <= r {
+            return self.sum[k];
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+
+        self.sum_inner(2 * k, a, m, l, r) + self.sum_inner(2 * k + 1, m, b, l, r)
+    }
+
+    fn min_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize) -> T {
+        if b <= l || r <= a {
+            return T::max_value();
+        }
+
+        if l <= a && b <= r {
+            return self.min[k];
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+
+        let left = self.min_inner(2 * k, a, m, l, r);
+        let right = self.min_inner(2 * k + 1, m, b, l, r);
+
+        if left <= right {
+            left
+        } else {
+            right
+        }
+    }
+
+    fn max_inner(&mut self, k: usize, a: usize, b: usize, l: usize, r: usize) -> T {
+        if b <= l || r <= a {
+            return T::min_value();
+        }
+
+        if l <= a && b <= r {
+            return self.max[k];
+        }
+
+        self.push(k);
+
+        let m = (a + b) / 2;
+
+        let left = self.max_inner(2 * k, a, m, l, r);
+        let right = self.max_inner(2 * k + 1, m, b, l, r);
+
+        if left >= right {
+            left
+        } else {
+            right
+        }
+    }
+
+    ///
+    /// 最大値のみを x にする（`second_max < x < max` のとき）
+    ///
+    fn apply_chmin(&mut self, k: usize, x: T) {
+        let count = T::from_usize(self.max_count[k]);
+        self.sum[k] = self.sum[k] - self.max[k] * count + x * count;
+
+        if self.min[k] == self.max[k] {
+            self.min[k] = x;
+        } else if self.second_min[k] == Some(self.max[k]) {
+            self.second_min[k] = Some(x);
+        }
+
+        self.max[k] = x;
+    }
+
+    ///
+    /// 最小値のみを x にする（`min < x < second_min` のとき）
+    ///
+    fn apply_chmax(&mut self, k: usize, x: T) {
+        let count = T::from_usize(self.min_count[k]);
+        self.sum[k] = self.sum[k] - self.min[k] * count + x * count;
+
+        if self.max[k] == self.min[k] {
+            self.max[k] = x;
+        } else if self.second_max[k] == Some(self.min[k]) {
+            self.second_max[k] = Some(x);
+        }
+
+        self.min[k] = x;
+    }
+
+    fn apply_add(&mut self, k: usize, x: T) {
+        self.sum[k] = self.sum[k] + x * T::from_usize(self.len[k]);
+
+        self.max[k] = self.max[k] + x;
+        self.second_max[k] = self.second_max[k].map(|second| second + x);
+
+        self.min[k] = self.min[k] + x;
+        self.second_min[k] = self.second_min[k].map(|second| second + x);
+
+        self.lazy[k] = self.lazy[k] + x;
+    }
+
+    fn push(&mut self, k: usize) {
+        if self.lazy[k] != T::zero() {
+            let x = self.lazy[k];
+
+            self.apply_add(2 * k, x);
+            self.apply_add(2 * k + 1, x);
+
+            self.lazy[k] = T::zero();
+        }
+
+        for c in [2 * k, 2 * k + 1] {
+            if self.max[c] > self.max[k] {
+                self.apply_chmin(c, self.max[k]);
+            }
+
+            if self.min[c] < self.min[k] {
+                self.apply_chmax(c, self.min[k]);
+            }
+        }
+    }
+
+    fn update(&mut self, k: usize) {
+        let (l, r) = (2 * k, 2 * k + 1);
+
+        // 範囲外の葉だけからなる子は無視する
+        if self.len[r] == 0 {
+            self.max[k] = self.max[l];
+            self.second_max[k] = self.second_max[l];
+            self.max_count[k] = self.max_count[l];
+            self.min[k] = self.min[l];
+            self.second_min[k] = self.second_min[l];
+            self.min_count[k] = self.min_count[l];
+            self.sum[k] = self.sum[l];
+            self.len[k] = self.len[l];
+            return;
+        }
+
+        self.sum[k] = self.sum[l] + self.sum[r];
+        self.len[k] = self.len[l] + self.len[r];
+
+        if self.max[l] > self.max[r] {
+            self.max[k] = self.max[l];
+            self.max_count[k] = self.max_count[l];
+            self.second_max[k] = larger(self.second_max[l], Some(self.max[r]));
+        } else if self.max[l] < self.max[r] {
+            self.max[k] = self.max[r];
+            self.max_count[k] = self.max_count[r];
+            self.second_max[k] = larger(Some(self.max[l]), self.second_max[r]);
+        } else {
+            self.max[k] = self.max[l];
+            self.max_count[k] = self.max_count[l] + self.max_count[r];
+            self.second_max[k] = larger(self.second_max[l], self.second_max[r]);
+        }
+
+        if self.min[l] < self.min[r] {
+            self.min[k] = self.min[l];
+            self.min_count[k] = self.min_count[l];
+            self.second_min[k] = smaller(self.second_min[l], Some(self.min[r]));
+        } else if self.min[l] > self.min[r] {
+            self.min[k] = self.min[r];
+            self.min_count[k] = self.min_count[r];
+            self.second_min[k] = smaller(Some(self.min[l]), self.second_min[r]);
+        } else {
+            self.min[k] = self.min[l];
+            self.min_count[k] = self.min_count[l] + self.min_count[r];
+            self.second_min[k] = smaller(self.second_min[l], self.second_min[r]);
+        }
+    }
+
+    fn to_half_open<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.n,
+        };
+
+        assert!(l <= r && r <= self.n, "range out of bounds");
+
+        (l, r)
+    }
+}
+
+impl<T> From<Vec<T>> for SegmentTreeBeats<T>
+where
+    T: Numeric + PartialOrd + Bounded + Sub<Output = T>,
+{
+    fn from(value: Vec<T>) -> Self {
+        let n = value.len();
+        let size = n.next_power_of_two();
+
+        // 範囲外の葉は空
+        let mut tree = SegmentTreeBeats {
+            n,
+            size,
+            max: vec![T::min_value(); 2 * size],
+            second_max: vec![None; 2 * size],
+            max_count: vec![0; 2 * size],
+            min: vec![T::max_value(); 2 * size],
+            second_min: vec![None; 2 * size],
+            min_count: vec![0; 2 * size],
+            sum: vec![T::zero(); 2 * size],
+            len: vec![0; 2 * size],
+            lazy: vec![T::zero(); 2 * size],
+        };
+
+        for (i, x) in value.into_iter().enumerate() {
+            let k = size + i;
+
+            tree.max[k] = x;
+            tree.max_count[k] = 1;
+            tree.min[k] = x;
+            tree.min_count[k] = 1;
+            tree.sum[k] = x;
+            tree.len[k] = 1;
+        }
+
+        for k in (1..size).rev() {
+            tree.update(k);
+        }
+
+        tree
+    }
+}
+
+fn larger<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a >= b { a } else { b }),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+fn smaller<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a <= b { a } else { b }),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SegmentTreeBeats;
+
+    enum Query<T> {
+        Chmin(T),
+        Chmax(T),
+        Add(T),
+        Assign(T),
+    }
+
+    ///
+    /// 全ての区間にクエリを順に適用し、愚直に計算した列と比べる
+    ///
+    fn check<T>(list: Vec<T>, queries: &[Query<T>])
+    where
+        T: super::Numeric + super::Bounded + Ord + std::fmt::Debug + std::ops::Sub<Output = T>,
+    {
+        let n = list.len();
+        let mut list = list;
+        let mut tree = SegmentTreeBeats::from(list.clone());
+
+        for query in queries.iter() {
+            for l in 0..=n {
+                for r in l..=n {
+                    match *query {
+                        Query::Chmin(x) => {
+                            tree.chmin(l..r, x);
+                            list[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+                        }
+                        Query::Chmax(x) => {
+                            tree.chmax(l..r, x);
+                            list[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+                        }
+                        Query::Add(x) => {
+                            tree.add(l..r, x);
+                            list[l..r].iter_mut().for_each(|v| *v = *v + x);
+                        }
+                        Query::Assign(x) => {
+                            tree.assign(l..r, x);
+                            list[l..r].iter_mut().for_each(|v| *v = x);
+                        }
+                    }
+
+                    for a in 0..=n {
+                        for b in a..=n {
+                            let sum = list[a..b].iter().fold(T::zero(), |s, &v| s + v);
+
+                            assert_eq!(tree.sum(a..b), sum);
+                            assert_eq!(
+                                tree.min(a..b),
+                                list[a..b].iter().copied().min().unwrap_or(T::max_value())
+                            );
+                            assert_eq!(
+                                tree.max(a..b),
+                                list[a..b].iter().copied().max().unwrap_or(T::min_value())
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed() {
+        use Query::*;
+
+        check(
+            vec![5_i64, -3, 8, 0, 2, -7],
+            &[
+                Chmin(4),
+                Add(-2),
+                Chmax(-1),
+                Chmin(1),
+                Add(3),
+                Assign(-5),
+                Chmax(2),
+                Assign(0),
+            ],
+        );
+
+        check(vec![3_i32], &[Chmin(1), Chmax(5), Add(-2), Assign(7)]);
+
+        // 型の最小値・最大値
+        let mut tree = SegmentTreeBeats::from(vec![3_i64, -1]);
+
+        tree.chmin(1.., i64::MIN);
+        tree.chmax(..1, i64::MAX);
+
+        assert_eq!(tree.sum(..), -1);
+        assert_eq!(tree.min(..), i64::MIN);
+        assert_eq!(tree.max(..), i64::MAX);
+        check(Vec::<i32>::new(), &[Chmin(1), Add(2)]);
+    }
+
+    #[test]
+    fn test_unsigned() {
+        use Query::*;
+
+        let mut tree = SegmentTreeBeats::<u64>::from(vec![0, 5]);
+
+        tree.add(.., 3);
+        tree.chmin(.., 2);
+
+        assert_eq!(tree.sum(..), 4);
+
+        tree.assign(.., 0);
+        tree.assign(1.., u64::MAX);
+
+        assert_eq!(tree.get(0), 0);
+        assert_eq!(tree.sum(..), u64::MAX);
+
+        check(
+            vec![0_u64, 5, 0, 3, 0],
+            &[
+                Add(3),
+                Chmin(2),
+                Assign(0),
+                Chmax(1),
+                Add(0),
+                Chmin(0),
+                Chmax(4),
+                Chmin(6),
+            ],
+        );
+
+        let mut tree = SegmentTreeBeats::<u64>::new(4);
+
+        tree.add(.., 10);
+        tree.chmin(1..3, 4);
+        tree.chmax(2.., 7);
+
+        assert_eq!(tree.sum(..), 10 + 4 + 7 + 10);
+        assert_eq!(tree.min(..), 4);
+        assert!(!tree.is_empty());
+        assert_eq!(tree.len(), 4);
+    }
+}
@@ -0,0 +1,336 @@
+use crate::{to_half_open, GenericBinaryIndexedTree, Group};
+use asakuchi_coordinate_compression::CoordinateCompression;
+use std::ops::{Bound, RangeBounds};
+
+///
+/// 2 次元 Binary Indexed Tree
+///
+/// 一点加算・長方形和を O(log h log w)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::{Additive, BinaryIndexedTree2D};
+///
+/// let mut tree = BinaryIndexedTree2D::<Additive<i64>>::new(3, 4);
+///
+/// tree.add(0, 0, 1);
+/// tree.add(1, 2, 10);
+/// tree.add(2, 3, 100);
+///
+/// assert_eq!(tree.sum(.., ..), 111);
+/// assert_eq!(tree.sum(1.., 2..), 110);
+/// assert_eq!(tree.sum(..2, ..3), 11);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct BinaryIndexedTree2D<G: Group> {
+    h: usize,
+    w: usize,
+    /// 1-indexed
+    bit: Vec<Vec<G::S>>,
+}
+
+impl<G: Group> BinaryIndexedTree2D<G> {
+    pub fn new(h: usize, w: usize) -> BinaryIndexedTree2D<G> {
+        BinaryIndexedTree2D {
+            h,
+            w,
+            bit: vec![vec![G::identity(); w + 1]; h + 1],
+        }
+    }
+
+    ///
+    /// 0-indexed の (i, j) に x を加える
+    ///
+    pub fn add(&mut self, i: usize, j: usize, x: G::S) {
+        assert!(i < self.h && j < self.w, "index out of bounds");
+
+        let mut a = i + 1;
+
+        while a <= self.h {
+            let mut b = j + 1;
+
+            while b <= self.w {
+                self.bit[a][b] = G::binary_operation(&self.bit[a][b], &x);
+
+                b += b & b.wrapping_neg();
+            }
+
+            a += a & a.wrapping_neg();
+        }
+    }
+
+    ///
+    /// 0-indexed の長方形 `rows` × `columns` の和
+    ///
+    pub fn sum<R, C>(&self, rows: R, columns: C) -> G::S
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (top, bottom) = to_half_open(rows, self.h);
+        let (left, right) = to_half_open(columns, self.w);
+
+        let positive =
+            G::binary_operation(&self.prefix_sum(bottom, right), &self.prefix_sum(top, left));
+        let negative =
+            G::binary_operation(&self.prefix_sum(top, right), &self.prefix_sum(bottom, left));
+
+        G::binary_operation(&positive, &G::inverse(&negative))
+    }
+
+    ///
+    /// [0, i) × [0, j) の和
+    ///
+    fn prefix_sum(&self, i: usize, j: usize) -> G::S {
+        let mut s = G::identity();
+
+        let mut a = i;
+
+        while a > 0 {
+            let mut b = j;
+
+            while b > 0 {
+                s = G::binary_operation(&s, &self.bit[a][b]);
+
+                b -= b & b.wrapping_neg();
+            }
+
+            a -= a & a.wrapping_neg();
+        }
+
+        s
+    }
+}
+
+///
+/// 座標圧縮した 2 次元 Binary Indexed Tree
+///
+/// 加算する点を先に全て与えるオフライン版。
+/// 点の数を N として、構築 O(N log N)、一点加算・長方形和 O(log² N)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::{Additive, CompressedBinaryIndexedTree2D};
+///
+/// let points = vec![(1_000_000_000, 5), (-3, 7), (20, -1)];
+///
+/// let mut tree = CompressedBinaryIndexedTree2D::<Additive<i64>, i64>::new(&points);
+///
+/// for &(x, y) in points.iter() {
+///     tree.add(x, y, 1);
+/// }
+///
+/// assert_eq!(tree.sum(0.., ..), 2);
+/// assert_eq!(tree.sum(-3..=20, 0..10), 1);
+/// ```
+///
+#[derive(Clone)]
+pub struct CompressedBinaryIndexedTree2D<G: Group, T: Ord + Copy> {
+    /// x 座標
+    xs: CoordinateCompression<T>,
+    /// ys[i] : BIT の i 番目のノードが担当する y 座標（昇順、重複なし）
+    ys: Vec<Vec<T>>,
+    bit: Vec<GenericBinaryIndexedTree<G>>,
+}
+
+// derive では G::S: Debug の制約が付かないので手で実装する
+impl<G: Group, T: Ord + Copy + std::fmt::Debug> std::fmt::Debug
+    for CompressedBinaryIndexedTree2D<G, T>
+where
+    GenericBinaryIndexedTree<G>: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompressedBinaryIndexedTree2D")
+            .field("xs", &self.xs)
+            .field("ys", &self.ys)
+            .field("bit", &self.bit)
+            .finish()
+    }
+}
+
+impl<G: Group, T: Ord + Copy> CompressedBinaryIndexedTree2D<G, T> {
+    ///
+    /// 加算する可能性のある点 (x, y) の一覧から構築する
+    ///
+    pub fn new(points: &[(T, T)]) -> CompressedBinaryIndexedTree2D<G, T> {
+        let xs = points
+            .iter()
+            .map(|&(x, _)| x)
+            .collect::<CoordinateCompression<_>>();
+
+        let mut sorted = points.to_vec();
+        sorted.sort_by_key(|&(_, y)| y);
+
+        let mut ys = vec![Vec::new(); xs.len() + 1];
+
+        for (x, y) in sorted {
+            let mut i = xs.index_of(&x) + 1;
+
+            while i <= xs.len() {
+                if ys[i].last() != Some(&y) {
+                    ys[i].push(y);
+                }
+
+                i += i & i.wrapping_neg();
+            }
+        }
+
+        let bit = ys
+            .iter()
+            .map(|list| GenericBinaryIndexedTree::new(list.len()))
+            .collect();
+
+        CompressedBinaryIndexedTree2D { xs, ys, bit }
+    }
+
+    ///
+    /// 点 (x, y) に v を加える
+    ///
+    /// 構築時に与えていない点なら panic
+    ///
+    pub fn add(&mut self, x: T, y: T, v: G::S) {
+        let mut i = self.xs.get_index(&x).expect("point is not registered") + 1;
+
+        while i <= self.xs.len() {
+            let j = self.ys[i]
+                .binary_search(&y)
+                .expect("point is not registered");
+
+            self.bit[i].add(j + 1, v.clone());
+
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    ///
+    /// x 座標が `x_range`、y 座標が `y_range` に含まれる点の和
+    ///
+    pub fn sum<R, Q>(&self, x_range: R, y_range: Q) -> G::S
+    where
+        R: RangeBounds<T>,
+        Q: RangeBounds<T>,
+    {
+        let (x_1, x_2) = Self::index_range(self.xs.values(), &x_range);
+
+        if x_1 >= x_2 {
+            return G::identity();
+        }
+
+        G::binary_operation(
+            &self.prefix_sum(x_2, &y_range),
+            &G::inverse(&self.prefix_sum(x_1, &y_range)),
+        )
+    }
+
+    ///
+    /// x 座標が小さい方から i 個の中で y 座標が `y_range` に含まれる点の和
+    ///
+    fn prefix_sum<Q: RangeBounds<T>>(&self, i: usize, y_range: &Q) -> G::S {
+        let mut s = G::identity();
+
+        let mut i = i;
+
+        while i > 0 {
+            let (y_1, y_2) = Self::index_range(&self.ys[i], y_range);
+
+            if y_1 < y_2 {
+                s = G::binary_operation(&s, &self.bit[i].range_sum(y_1..y_2));
+            }
+
+            i -= i & i.wrapping_neg();
+        }
+
+        s
+    }
+
+    ///
+    /// 座標の区間を添字の区間に変換する
+    ///
+    fn index_range<R: RangeBounds<T>>(list: &[T], range: &R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Bound::Included(x) => list.partition_point(|v| v < x),
+            Bound::Excluded(x) => list.partition_point(|v| v <= x),
+            Bound::Unbounded => 0,
+        };
+
+        let r = match range.end_bound() {
+            Bound::Included(x) => list.partition_point(|v| v <= x),
+            Bound::Excluded(x) => list.partition_point(|v| v < x),
+            Bound::Unbounded => list.len(),
+        };
+
+        (l, r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryIndexedTree2D, CompressedBinaryIndexedTree2D};
+    use crate::Additive;
+
+    #[test]
+    fn test_2d() {
+        let (h, w) = (4, 5);
+        let mut grid = vec![vec![0_i64; w]; h];
+        let mut tree = BinaryIndexedTree2D::<Additive<i64>>::new(h, w);
+
+        for &(i, j, x) in [(0, 0, 3), (1, 3, -2), (3, 4, 7), (2, 2, 5), (1, 3, 4)].iter() {
+            tree.add(i, j, x);
+            grid[i][j] += x;
+        }
+
+        for top in 0..=h {
+            for bottom in top..=h {
+                for left in 0..=w {
+                    for right in left..=w {
+                        let expected = grid[top..bottom]
+                            .iter()
+                            .map(|row| row[left..right].iter().sum::<i64>())
+                            .sum::<i64>();
+
+                        assert_eq!(tree.sum(top..bottom, left..right), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compressed_2d() {
+        let points = vec![
+            (5, 100),
+            (-7, 3),
+            (5, -20),
+            (1_000_000_000, 3),
+            (0, 0),
+            (-7, 3),
+            (12, 50),
+        ];
+
+        let mut tree = CompressedBinaryIndexedTree2D::<Additive<i64>, i64>::new(&points);
+
+        for (i, &(x, y)) in points.iter().enumerate() {
+            tree.add(x, y, i as i64 + 1);
+        }
+
+        let coordinates = [-100, -7, 0, 3, 5, 12, 50, 100, 1_000_000_001];
+
+        for &x_1 in coordinates.iter() {
+            for &x_2 in coordinates.iter() {
+                for &y_1 in coordinates.iter() {
+                    for &y_2 in coordinates.iter() {
+                        let expected = points
+                            .iter()
+                            .enumerate()
+                            .filter(|&(_, &(x, y))| x_1 <= x && x < x_2 && y_1 <= y && y < y_2)
+                            .map(|(i, _)| i as i64 + 1)
+                            .sum::<i64>();
+
+                        assert_eq!(tree.sum(x_1..x_2, y_1..y_2), expected);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(tree.sum(..=5, 3..=3), 2 + 6);
+    }
+}
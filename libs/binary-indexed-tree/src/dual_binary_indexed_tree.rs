@@ -0,0 +1,130 @@
+use crate::{to_half_open, GenericBinaryIndexedTree, Group};
+use std::ops::RangeBounds;
+
+///
+/// 区間加算・一点取得の Binary Indexed Tree
+///
+/// 差分を BIT で持つ
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::{Additive, DualBinaryIndexedTree};
+///
+/// let mut tree = DualBinaryIndexedTree::<Additive<i64>>::from(vec![1, 2, 3, 4]);
+///
+/// tree.add(1..3, 10);
+/// tree.add(2.., -1);
+///
+/// assert_eq!(tree.get(0), 1);
+/// assert_eq!(tree.get(1), 12);
+/// assert_eq!(tree.get(2), 12);
+/// assert_eq!(tree.get(3), 3);
+/// ```
+///
+#[derive(Clone)]
+pub struct DualBinaryIndexedTree<G: Group> {
+    /// 差分
+    diff: GenericBinaryIndexedTree<G>,
+}
+
+// derive では G::S: Debug の制約が付かないので手で実装する
+impl<G: Group> std::fmt::Debug for DualBinaryIndexedTree<G>
+where
+    GenericBinaryIndexedTree<G>: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DualBinaryIndexedTree")
+            .field("diff", &self.diff)
+            .finish()
+    }
+}
+
+impl<G: Group> DualBinaryIndexedTree<G> {
+    pub fn new(n: usize) -> DualBinaryIndexedTree<G> {
+        DualBinaryIndexedTree {
+            diff: GenericBinaryIndexedTree::new(n),
+        }
+    }
+
+    ///
+    /// 0-indexed の区間 `range` に x を加える
+    ///
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: G::S) {
+        let (l, r) = to_half_open(range, self.len());
+
+        if l == r {
+            return;
+        }
+
+        self.diff.add(l + 1, x.clone());
+
+        if r < self.len() {
+            self.diff.add(r + 1, G::inverse(&x));
+        }
+    }
+
+    ///
+    /// 0-indexed で i 番目の値
+    ///
+    pub fn get(&self, i: usize) -> G::S {
+        self.diff.range_sum(..=i)
+    }
+
+    pub fn len(&self) -> usize {
+        self.diff.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+}
+
+///
+/// 0-indexed の値の列から O(n) で構築する
+///
+impl<G: Group> From<Vec<G::S>> for DualBinaryIndexedTree<G> {
+    fn from(value: Vec<G::S>) -> Self {
+        let mut diff = Vec::with_capacity(value.len());
+
+        for i in 0..value.len() {
+            if i == 0 {
+                diff.push(value[0].clone());
+            } else {
+                diff.push(G::binary_operation(&value[i], &G::inverse(&value[i - 1])));
+            }
+        }
+
+        DualBinaryIndexedTree {
+            diff: GenericBinaryIndexedTree::from(diff),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DualBinaryIndexedTree;
+    use crate::Xor;
+
+    #[test]
+    fn test_dual() {
+        let mut list = vec![0b0001_u8, 0b0010, 0b0100, 0b1000, 0b0000];
+        let mut tree = DualBinaryIndexedTree::<Xor<u8>>::from(list.clone());
+
+        for &(l, r, x) in [(0, 3, 0b1111), (2, 5, 0b0101), (4, 5, 0b0011), (1, 1, 0b1)].iter() {
+            tree.add(l..r, x);
+
+            for value in list[l..r].iter_mut() {
+                *value ^= x;
+            }
+
+            for (i, &value) in list.iter().enumerate() {
+                assert_eq!(tree.get(i), value);
+            }
+        }
+
+        let mut tree = DualBinaryIndexedTree::<Xor<u8>>::new(3);
+
+        tree.add(.., 7);
+        assert_eq!(tree.get(2), 7);
+        assert_eq!(tree.len(), 3);
+    }
+}
@@ -0,0 +1,78 @@
+use std::marker::PhantomData;
+
+///
+/// 可換群
+///
+pub trait Group {
+    ///
+    /// 群の型
+    ///
+    type S: Clone;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S;
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+
+    ///
+    /// 逆元
+    ///
+    fn inverse(a: &Self::S) -> Self::S;
+}
+
+///
+/// 加法群
+///
+/// 整数、浮動小数点数、ModInt など
+///
+#[derive(Debug, Clone)]
+pub struct Additive<T>(PhantomData<T>);
+
+impl<T> Group for Additive<T>
+where
+    T: Clone + Default + std::ops::Add<Output = T> + std::ops::Neg<Output = T>,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::default()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        a.clone() + b.clone()
+    }
+
+    fn inverse(a: &Self::S) -> Self::S {
+        -a.clone()
+    }
+}
+
+///
+/// XOR による群
+///
+#[derive(Debug, Clone)]
+pub struct Xor<T>(PhantomData<T>);
+
+impl<T> Group for Xor<T>
+where
+    T: Clone + Default + std::ops::BitXor<Output = T>,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::default()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        a.clone() ^ b.clone()
+    }
+
+    fn inverse(a: &Self::S) -> Self::S {
+        a.clone()
+    }
+}
@@ -0,0 +1,249 @@
+use crate::to_half_open;
+use std::ops::{Add, RangeBounds, Sub};
+
+///
+/// いもす法
+///
+/// 区間加算を O(1) で積んでおき、最後に O(n) でまとめて値を求める
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::Imos1D;
+///
+/// let mut imos = Imos1D::new(5);
+///
+/// imos.add(1..4, 2);
+/// imos.add(3.., 10);
+///
+/// assert_eq!(imos.build(), vec![0, 2, 2, 12, 10]);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Imos1D<T> {
+    n: usize,
+    /// 加える差分（長さ n + 1）
+    plus: Vec<T>,
+    /// 引く差分（長さ n + 1）
+    minus: Vec<T>,
+}
+
+impl<T> Imos1D<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(n: usize) -> Imos1D<T> {
+        Imos1D {
+            n,
+            plus: vec![T::default(); n + 1],
+            minus: vec![T::default(); n + 1],
+        }
+    }
+
+    ///
+    /// 0-indexed の区間 `range` に x を加える
+    ///
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = to_half_open(range, self.n);
+
+        // 符号なし整数でも負にならないよう、足す分と引く分を分けて持つ
+        self.plus[l] = self.plus[l].clone() + x.clone();
+        self.minus[r] = self.minus[r].clone() + x;
+    }
+
+    ///
+    /// 各位置の値
+    ///
+    pub fn build(&self) -> Vec<T> {
+        let mut result = Vec::with_capacity(self.n);
+        let mut current = T::default();
+
+        for i in 0..self.n {
+            current = current + self.plus[i].clone() - self.minus[i].clone();
+            result.push(current.clone());
+        }
+
+        result
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+///
+/// 2 次元いもす法
+///
+/// 長方形加算を O(1) で積んでおき、最後に O(hw) でまとめて値を求める
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::Imos2D;
+///
+/// let mut imos = Imos2D::new(2, 3);
+///
+/// imos.add(.., 1.., 1);
+/// imos.add(1.., ..=1, 10);
+///
+/// assert_eq!(imos.build(), vec![vec![0, 1, 1], vec![10, 11, 1]]);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Imos2D<T> {
+    h: usize,
+    w: usize,
+    /// 加える差分（(h + 1) × (w + 1)）
+    plus: Vec<Vec<T>>,
+    /// 引く差分（(h + 1) × (w + 1)）
+    minus: Vec<Vec<T>>,
+}
+
+impl<T> Imos2D<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new(h: usize, w: usize) -> Imos2D<T> {
+        Imos2D {
+            h,
+            w,
+            plus: vec![vec![T::default(); w + 1]; h + 1],
+            minus: vec![vec![T::default(); w + 1]; h + 1],
+        }
+    }
+
+    ///
+    /// 0-indexed の長方形 `rows` × `columns` に x を加える
+    ///
+    pub fn add<R, C>(&mut self, rows: R, columns: C, x: T)
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (top, bottom) = to_half_open(rows, self.h);
+        let (left, right) = to_half_open(columns, self.w);
+
+        // 符号なし整数でも負にならないよう、足す分と引く分を分けて持つ
+        self.plus[top][left] = self.plus[top][left].clone() + x.clone();
+        self.minus[top][right] = self.minus[top][right].clone() + x.clone();
+        self.minus[bottom][left] = self.minus[bottom][left].clone() + x.clone();
+        self.plus[bottom][right] = self.plus[bottom][right].clone() + x;
+    }
+
+    ///
+    /// 各位置の値
+    ///
+    pub fn build(&self) -> Vec<Vec<T>> {
+        let mut result = vec![vec![T::default(); self.w]; self.h];
+
+        for i in 0..self.h {
+            for j in 0..self.w {
+                // 足す分を先にまとめ、途中で負にならないようにする
+                let mut value = self.plus[i][j].clone();
+
+                if i > 0 {
+                    value = value + result[i - 1][j].clone();
+                }
+
+                if j > 0 {
+                    value = value + result[i][j - 1].clone();
+                }
+
+                if i > 0 && j > 0 {
+                    value = value - result[i - 1][j - 1].clone();
+                }
+
+                result[i][j] = value - self.minus[i][j].clone();
+            }
+        }
+
+        result
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Imos1D, Imos2D};
+    use ac_library::ModInt998244353 as Mint;
+
+    #[test]
+    fn test_imos_1d() {
+        let n = 8;
+        let mut imos = Imos1D::new(n);
+        let mut expected = vec![0_i64; n];
+
+        for &(l, r, x) in [(0, 8, 1), (2, 5, -4), (7, 8, 9), (3, 3, 100), (1, 6, 2)].iter() {
+            imos.add(l..r, x);
+
+            for value in expected[l..r].iter_mut() {
+                *value += x;
+            }
+        }
+
+        assert_eq!(imos.build(), expected);
+        assert_eq!(imos.len(), n);
+
+        // 区間の重なりを数える
+        let mut imos = Imos1D::<usize>::new(4);
+        imos.add(0..2, 1);
+        imos.add(1..4, 1);
+        imos.add(3..3, 5);
+
+        assert_eq!(imos.build(), vec![1, 2, 1, 1]);
+
+        let mut imos = Imos1D::new(3);
+        imos.add(.., Mint::new(998_244_352));
+        imos.add(1..2, Mint::new(3));
+
+        assert_eq!(
+            imos.build(),
+            vec![Mint::new(998_244_352), Mint::new(2), Mint::new(998_244_352)]
+        );
+    }
+
+    #[test]
+    fn test_imos_2d() {
+        let (h, w) = (4, 5);
+        let mut imos = Imos2D::new(h, w);
+        let mut expected = vec![vec![0_i64; w]; h];
+
+        for &(top, bottom, left, right, x) in [
+            (0, 4, 0, 5, 1),
+            (1, 3, 2, 5, -3),
+            (3, 4, 4, 5, 7),
+            (0, 2, 1, 1, 100),
+            (2, 4, 0, 3, 5),
+        ]
+        .iter()
+        {
+            imos.add(top..bottom, left..right, x);
+
+            for row in expected[top..bottom].iter_mut() {
+                for value in row[left..right].iter_mut() {
+                    *value += x;
+                }
+            }
+        }
+
+        assert_eq!(imos.build(), expected);
+        assert_eq!((imos.height(), imos.width()), (h, w));
+
+        let mut imos = Imos2D::<usize>::new(3, 3);
+        imos.add(..2, ..2, 1);
+        imos.add(1.., 1.., 1);
+        imos.add(2.., .., 3);
+
+        assert_eq!(
+            imos.build(),
+            vec![vec![1, 1, 0], vec![1, 2, 1], vec![3, 4, 4]]
+        );
+    }
+}
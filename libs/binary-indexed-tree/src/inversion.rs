@@ -0,0 +1,145 @@
+use crate::OrderedMultiset;
+use asakuchi_coordinate_compression::CoordinateCompression;
+
+///
+/// 転倒数
+///
+/// i < j かつ list[i] > list[j] となる組の数。
+/// 座標圧縮するので値の範囲や重複は問わない
+///
+/// O(n log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::inversion;
+///
+/// assert_eq!(inversion(&[3, 1, 5, 4, 2]), 5);
+/// assert_eq!(inversion(&["b", "a", "b", "a"]), 3);
+/// ```
+///
+pub fn inversion<T: Ord>(list: &[T]) -> usize {
+    *inversion_prefix(list).last().unwrap()
+}
+
+///
+/// 各接頭辞の転倒数
+///
+/// `result[i]` は `list[..i]` の転倒数（長さ n + 1）
+///
+/// O(n log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::inversion_prefix;
+///
+/// assert_eq!(inversion_prefix(&[3, 1, 5, 4, 2]), vec![0, 0, 1, 1, 2, 5]);
+/// ```
+///
+pub fn inversion_prefix<T: Ord>(list: &[T]) -> Vec<usize> {
+    let compression = CoordinateCompression::new(list);
+
+    let mut set = OrderedMultiset::new(compression.len());
+
+    let mut result = Vec::with_capacity(list.len() + 1);
+    result.push(0);
+
+    for x in list {
+        let x = compression.index_of(&x);
+
+        // これまでの要素のうち x より大きいもの
+        let count = set.len() - set.count_less(x + 1);
+
+        result.push(result.last().unwrap() + count);
+
+        set.insert(x);
+    }
+
+    result
+}
+
+///
+/// 隣接する要素の交換で a を b に並べ替える最小の回数
+///
+/// b が a の並べ替えでなければ None
+///
+/// O(n log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::min_adjacent_swaps;
+///
+/// assert_eq!(min_adjacent_swaps(&[1, 2, 1, 3], &[1, 1, 3, 2]), Some(2));
+/// assert_eq!(min_adjacent_swaps(&[1, 2], &[1, 1]), None);
+/// ```
+///
+pub fn min_adjacent_swaps<T: Ord>(a: &[T], b: &[T]) -> Option<usize> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    // 同じ値は順番を保って対応させる
+    let mut sorted_a = (0..a.len()).collect::<Vec<_>>();
+    sorted_a.sort_by(|&i, &j| a[i].cmp(&a[j]).then(i.cmp(&j)));
+
+    let mut sorted_b = (0..b.len()).collect::<Vec<_>>();
+    sorted_b.sort_by(|&i, &j| b[i].cmp(&b[j]).then(i.cmp(&j)));
+
+    // target[i] : a[i] の移動先
+    let mut target = vec![0; a.len()];
+
+    for (&i, &j) in sorted_a.iter().zip(sorted_b.iter()) {
+        if a[i] != b[j] {
+            return None;
+        }
+
+        target[i] = j;
+    }
+
+    Some(inversion(&target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inversion, inversion_prefix, min_adjacent_swaps};
+
+    #[test]
+    fn test_inversion() {
+        assert_eq!(inversion(&[3, 1, 5, 4, 2]), 5);
+        assert_eq!(inversion(&[1, 2, 3, 4, 5, 6]), 0);
+        assert_eq!(inversion(&[7, 6, 5, 4, 3, 2, 1]), 21);
+        assert_eq!(
+            inversion(&[19, 11, 10, 7, 8, 9, 17, 18, 20, 4, 3, 15, 16, 1, 5, 14, 6, 2, 13, 12]),
+            114
+        );
+
+        // 重複、大きな値、負の値
+        assert_eq!(inversion(&[2, 2, 1, 1]), 4);
+        assert_eq!(
+            inversion(&[1_000_000_000_000_i64, -5, 1_000_000_000_000, -5]),
+            3
+        );
+        assert_eq!(inversion::<u8>(&[]), 0);
+
+        let list = [5, 3, 3, 8, 1, 3, 9, 0];
+
+        assert_eq!(
+            inversion_prefix(&list),
+            (0..=list.len())
+                .map(|i| {
+                    (0..i)
+                        .map(|j| (j + 1..i).filter(|&k| list[j] > list[k]).count())
+                        .sum::<usize>()
+                })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_min_adjacent_swaps() {
+        assert_eq!(min_adjacent_swaps(&[1, 2, 3], &[3, 2, 1]), Some(3));
+        assert_eq!(min_adjacent_swaps(&[1, 2, 3], &[1, 2, 3]), Some(0));
+        assert_eq!(
+            min_adjacent_swaps(&['a', 'b', 'a', 'b'], &['b', 'b', 'a', 'a']),
+            Some(3)
+        );
+        assert_eq!(min_adjacent_swaps(&[1, 2, 2], &[1, 1, 2]), None);
+        assert_eq!(min_adjacent_swaps(&[1, 2], &[1, 2, 3]), None);
+    }
+}
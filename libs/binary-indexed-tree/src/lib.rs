@@ -4,43 +4,118 @@
 //! 参考：<https://algo-logic.info/binary-indexed-tree/>
 //!
 
+mod binary_indexed_tree_2d;
+mod dual_binary_indexed_tree;
+mod group;
+mod imos;
+mod inversion;
+mod longest_increasing_subsequence;
+mod monoid;
+mod offline_rectangle_sum;
+mod ordered_multiset;
+mod prefix_binary_indexed_tree;
+mod prefix_sum;
+mod range_add_binary_indexed_tree;
+
+pub use binary_indexed_tree_2d::{BinaryIndexedTree2D, CompressedBinaryIndexedTree2D};
+pub use dual_binary_indexed_tree::DualBinaryIndexedTree;
+pub use group::{Additive, Group, Xor};
+pub use imos::{Imos1D, Imos2D};
+pub use inversion::{inversion, inversion_prefix, min_adjacent_swaps};
+pub use longest_increasing_subsequence::{
+    longest_increasing_subsequence, longest_non_decreasing_subsequence,
+    LongestIncreasingSubsequence,
+};
+pub use monoid::{Bounded, Max, Min, Monoid};
+pub use offline_rectangle_sum::{dominance_count, OfflineRectangleSum};
+pub use ordered_multiset::OrderedMultiset;
+pub use prefix_binary_indexed_tree::{
+    PrefixBinaryIndexedTree, PrefixMaxBinaryIndexedTree, PrefixMinBinaryIndexedTree,
+};
+pub use prefix_sum::{PrefixSum, PrefixSum2D};
+pub use range_add_binary_indexed_tree::{FromUsize, RangeAddBinaryIndexedTree};
+
+use std::ops::{Bound, RangeBounds};
+
 ///
 /// Binary Indexed Tree (BIT)
 ///
-pub struct BinaryIndexedTree {
+pub type BinaryIndexedTree = GenericBinaryIndexedTree<Additive<isize>>;
+
+///
+/// 可換群 `G` の Binary Indexed Tree (BIT)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::{GenericBinaryIndexedTree, Xor};
+///
+/// let tree = GenericBinaryIndexedTree::<Xor<u32>>::from(vec![0b001, 0b011, 0b110, 0b100]);
+///
+/// assert_eq!(tree.range_sum(1..3), 0b101);
+/// assert_eq!(tree.range_sum(..), 0b000);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct GenericBinaryIndexedTree<G: Group> {
     n: usize,
-    bit: Vec<isize>,
+    bit: Vec<G::S>,
 }
 
-impl BinaryIndexedTree {
-    pub fn new(n: usize) -> BinaryIndexedTree {
-        let bit = vec![0; n + 1];
+impl<G: Group> GenericBinaryIndexedTree<G> {
+    pub fn new(n: usize) -> GenericBinaryIndexedTree<G> {
+        let bit = vec![G::identity(); n + 1];
 
-        BinaryIndexedTree { n: n + 1, bit }
+        GenericBinaryIndexedTree { n: n + 1, bit }
     }
 
-    pub fn add(&mut self, i: usize, x: isize) {
+    pub fn add(&mut self, i: usize, x: G::S) {
         assert_ne!(i, 0, "i is 1-index");
 
         let mut index = i as isize;
 
         while (index as usize) < self.n {
-            self.bit[index as usize] += x;
+            self.bit[index as usize] = G::binary_operation(&self.bit[index as usize], &x);
 
             // i & -i => i の最後の1のビット
             index += index & -index;
         }
     }
 
-    pub fn sum(&self, i: usize) -> isize {
+    pub fn sum(&self, i: usize) -> G::S {
         assert_ne!(i, 0, "i is 1-index");
 
-        let mut s = 0;
+        self.prefix_sum(i)
+    }
+
+    ///
+    /// 0-indexed の区間 `range` の和
+    ///
+    pub fn range_sum<R: RangeBounds<usize>>(&self, range: R) -> G::S {
+        let (l, r) = to_half_open(range, self.len());
+
+        G::binary_operation(&self.prefix_sum(r), &G::inverse(&self.prefix_sum(l)))
+    }
+
+    ///
+    /// 要素数
+    ///
+    pub fn len(&self) -> usize {
+        self.n - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// 1-indexed で [1, i] の和（i = 0 なら単位元）
+    ///
+    fn prefix_sum(&self, i: usize) -> G::S {
+        let mut s = G::identity();
 
         let mut index = i as isize;
 
         while index > 0 {
-            s += self.bit[index as usize];
+            s = G::binary_operation(&s, &self.bit[index as usize]);
 
             // i & -i => i の最後の1のビット
             index -= index & -index;
@@ -50,9 +125,54 @@ impl BinaryIndexedTree {
     }
 }
 
+///
+/// 0-indexed の区間を半開区間 [l, r) に変換する
+///
+pub(crate) fn to_half_open<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let l = match range.start_bound() {
+        Bound::Included(&l) => l,
+        Bound::Excluded(&l) => l + 1,
+        Bound::Unbounded => 0,
+    };
+
+    let r = match range.end_bound() {
+        Bound::Included(&r) => r + 1,
+        Bound::Excluded(&r) => r,
+        Bound::Unbounded => len,
+    };
+
+    assert!(l <= r && r <= len, "range out of bounds");
+
+    (l, r)
+}
+
+///
+/// 0-indexed の値の列から O(n) で構築する
+///
+impl<G: Group> From<Vec<G::S>> for GenericBinaryIndexedTree<G> {
+    fn from(value: Vec<G::S>) -> Self {
+        let n = value.len() + 1;
+
+        let mut bit = Vec::with_capacity(n);
+        bit.push(G::identity());
+        bit.extend(value);
+
+        for i in 1..n {
+            // 直上のノードへ加える
+            let j = i + (i & i.wrapping_neg());
+
+            if j < n {
+                bit[j] = G::binary_operation(&bit[j], &bit[i]);
+            }
+        }
+
+        GenericBinaryIndexedTree { n, bit }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::BinaryIndexedTree;
+    use super::{Additive, BinaryIndexedTree, GenericBinaryIndexedTree};
 
     #[test]
     fn test_bit() {
@@ -72,32 +192,33 @@ mod tests {
     }
 
     #[test]
-    fn test_inversion() {
-        assert_eq!(inversion(5, vec![3, 1, 5, 4, 2]), 5);
-        assert_eq!(inversion(6, vec![1, 2, 3, 4, 5, 6]), 0);
-        assert_eq!(inversion(7, vec![7, 6, 5, 4, 3, 2, 1]), 21);
-        assert_eq!(
-            inversion(
-                20,
-                vec![19, 11, 10, 7, 8, 9, 17, 18, 20, 4, 3, 15, 16, 1, 5, 14, 6, 2, 13, 12]
-            ),
-            114
-        );
-    }
+    fn test_generic() {
+        let list = vec![3_i128, -1, 4, 1, -5, 9, 2, -6];
 
-    fn inversion(n: usize, a: Vec<usize>) -> isize {
-        // 転倒数を求める
+        let tree = GenericBinaryIndexedTree::<Additive<i128>>::from(list.clone());
+        let mut expected = GenericBinaryIndexedTree::<Additive<i128>>::new(list.len());
 
-        let mut bit = BinaryIndexedTree::new(n);
-
-        let mut inversion = 0;
+        for (i, &x) in list.iter().enumerate() {
+            expected.add(i + 1, x);
+        }
 
-        for x in a {
-            inversion += bit.sum(n) - bit.sum(x);
+        for l in 0..=list.len() {
+            for r in l..=list.len() {
+                let sum = list[l..r].iter().sum::<i128>();
 
-            bit.add(x, 1);
+                assert_eq!(tree.range_sum(l..r), sum);
+                assert_eq!(expected.range_sum(l..r), sum);
+            }
         }
 
-        inversion
+        assert_eq!(tree.range_sum(2..=4), 0);
+        assert_eq!(tree.range_sum(..), 7);
+        assert_eq!(tree.range_sum(5..), 5);
+
+        let mut tree = GenericBinaryIndexedTree::<Additive<f64>>::new(3);
+
+        tree.add(2, 0.5);
+        tree.add(3, 1.25);
+        assert_eq!(tree.range_sum(1..), 1.75);
     }
 }
@@ -0,0 +1,147 @@
+use crate::PrefixMaxBinaryIndexedTree;
+use asakuchi_coordinate_compression::CoordinateCompression;
+
+///
+/// 最長増加部分列
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LongestIncreasingSubsequence {
+    /// 最長増加部分列の長さ
+    pub length: usize,
+    /// 最長増加部分列の 1 つ（元の列の添字、昇順）
+    pub indices: Vec<usize>,
+    /// lengths[i] : i 番目の要素で終わる増加部分列の最大の長さ
+    pub lengths: Vec<usize>,
+}
+
+///
+/// 最長増加部分列（狭義単調増加）
+///
+/// O(n log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::longest_increasing_subsequence;
+///
+/// let lis = longest_increasing_subsequence(&[3, 1, 4, 1, 5, 9, 2, 6]);
+///
+/// assert_eq!(lis.length, 4);
+/// assert_eq!(lis.indices, vec![1, 2, 4, 5]);
+/// assert_eq!(lis.lengths, vec![1, 1, 2, 1, 3, 4, 2, 4]);
+/// ```
+///
+pub fn longest_increasing_subsequence<T: Ord>(list: &[T]) -> LongestIncreasingSubsequence {
+    solve(list, true)
+}
+
+///
+/// 最長非減少部分列（広義単調増加）
+///
+/// O(n log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::longest_non_decreasing_subsequence;
+///
+/// let lis = longest_non_decreasing_subsequence(&[3, 1, 4, 1, 5, 9, 2, 6]);
+///
+/// assert_eq!(lis.length, 4);
+/// assert_eq!(lis.lengths, vec![1, 1, 2, 2, 3, 4, 3, 4]);
+/// ```
+///
+pub fn longest_non_decreasing_subsequence<T: Ord>(list: &[T]) -> LongestIncreasingSubsequence {
+    solve(list, false)
+}
+
+fn solve<T: Ord>(list: &[T], strict: bool) -> LongestIncreasingSubsequence {
+    let compression = CoordinateCompression::new(list);
+
+    // 値ごとに (その値で終わる部分列の最大の長さ, 末尾の添字)
+    let mut tree = PrefixMaxBinaryIndexedTree::<(usize, usize)>::new(compression.len());
+
+    let mut lengths = Vec::with_capacity(list.len());
+    let mut previous = Vec::with_capacity(list.len());
+
+    for (i, x) in list.iter().enumerate() {
+        let x = compression.index_of(&x);
+
+        let (length, j) = tree.prefix(if strict { x } else { x + 1 });
+
+        lengths.push(length + 1);
+        previous.push(if length == 0 { None } else { Some(j) });
+
+        tree.update(x, (length + 1, i));
+    }
+
+    let length = lengths.iter().copied().max().unwrap_or(0);
+
+    let mut indices = Vec::with_capacity(length);
+    let mut current = lengths.iter().position(|&l| l == length);
+
+    while let Some(i) = current {
+        indices.push(i);
+        current = previous[i];
+    }
+
+    indices.reverse();
+
+    LongestIncreasingSubsequence {
+        length,
+        indices,
+        lengths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{longest_increasing_subsequence, longest_non_decreasing_subsequence};
+
+    #[test]
+    fn test_compare_with_all_subsequences() {
+        let lists = vec![
+            vec![],
+            vec![7],
+            vec![2, 2, 2, 2],
+            vec![5, 4, 3, 2, 1],
+            vec![1, 2, 3, 4, 5, 6],
+            vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3],
+            vec![0, 8, 4, 12, 2, 10, 6, 14, 1, 9],
+            vec![1, 3, 3, 2, 2, 4, 4, 1, 5],
+        ];
+
+        for list in lists {
+            let n = list.len();
+
+            for strict in [true, false] {
+                let result = if strict {
+                    longest_increasing_subsequence(&list)
+                } else {
+                    longest_non_decreasing_subsequence(&list)
+                };
+
+                let valid = |a: usize, b: usize| if strict { a < b } else { a <= b };
+
+                // 全ての部分列を調べる
+                let mut expected = vec![0; n];
+
+                for mask in 1_usize..1 << n {
+                    let indices = (0..n).filter(|&i| mask >> i & 1 == 1).collect::<Vec<_>>();
+
+                    if indices.windows(2).all(|w| valid(list[w[0]], list[w[1]])) {
+                        let last = *indices.last().unwrap();
+
+                        expected[last] = expected[last].max(indices.len());
+                    }
+                }
+
+                assert_eq!(result.lengths, expected);
+                assert_eq!(result.length, expected.iter().copied().max().unwrap_or(0));
+
+                assert_eq!(result.indices.len(), result.length);
+                assert!(result.indices.windows(2).all(|w| w[0] < w[1]));
+                assert!(result
+                    .indices
+                    .windows(2)
+                    .all(|w| valid(list[w[0]], list[w[1]])));
+            }
+        }
+    }
+}
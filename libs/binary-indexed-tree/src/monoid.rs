@@ -0,0 +1,99 @@
+use std::marker::PhantomData;
+
+///
+/// 可換モノイド
+///
+pub trait Monoid {
+    ///
+    /// モノイドの型
+    ///
+    type S: Clone;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S;
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+///
+/// 最小値・最大値の単位元に使う上限・下限
+///
+pub trait Bounded {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_bounded {
+    ($($t:ty),*) => {
+        $(impl Bounded for $t {
+            fn min_value() -> Self {
+                <$t>::MIN
+            }
+
+            fn max_value() -> Self {
+                <$t>::MAX
+            }
+        })*
+    };
+}
+impl_bounded!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+///
+/// 辞書順
+///
+impl<A: Bounded, B: Bounded> Bounded for (A, B) {
+    fn min_value() -> Self {
+        (A::min_value(), B::min_value())
+    }
+
+    fn max_value() -> Self {
+        (A::max_value(), B::max_value())
+    }
+}
+
+///
+/// 最小値
+///
+#[derive(Debug, Clone)]
+pub struct Min<T>(PhantomData<T>);
+
+impl<T> Monoid for Min<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::max_value()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        *a.min(b)
+    }
+}
+
+///
+/// 最大値
+///
+#[derive(Debug, Clone)]
+pub struct Max<T>(PhantomData<T>);
+
+impl<T> Monoid for Max<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::min_value()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        *a.max(b)
+    }
+}
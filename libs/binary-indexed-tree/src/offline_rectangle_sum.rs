@@ -0,0 +1,266 @@
+use crate::{Additive, GenericBinaryIndexedTree, Group};
+use asakuchi_coordinate_compression::CoordinateCompression;
+use std::ops::{Bound, RangeBounds};
+
+/// 座標の区間 (始点, 終点)
+type Interval<T> = (Bound<T>, Bound<T>);
+
+///
+/// オフラインの長方形和
+///
+/// 重み付きの点と長方形クエリをまとめて与え、x 座標で平面走査して BIT で答える。
+/// 点の数を N、クエリの数を Q として O((N + Q) log N)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::{Additive, OfflineRectangleSum};
+///
+/// let mut solver = OfflineRectangleSum::<Additive<i64>, i64>::new();
+///
+/// solver.add_point(0, 0, 1);
+/// solver.add_point(5, 3, 10);
+/// solver.add_point(1_000_000_000, -2, 100);
+///
+/// solver.add_query(0..=5, ..);
+/// solver.add_query(1.., ..0);
+///
+/// assert_eq!(solver.solve(), vec![11, 100]);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct OfflineRectangleSum<G: Group, T: Ord + Copy> {
+    points: Vec<(T, T, G::S)>,
+    /// (x 座標の区間, y 座標の区間)
+    queries: Vec<(Interval<T>, Interval<T>)>,
+}
+
+impl<G: Group, T: Ord + Copy> OfflineRectangleSum<G, T> {
+    pub fn new() -> OfflineRectangleSum<G, T> {
+        OfflineRectangleSum {
+            points: Vec::new(),
+            queries: Vec::new(),
+        }
+    }
+
+    ///
+    /// 点 (x, y) に重み w を加える
+    ///
+    pub fn add_point(&mut self, x: T, y: T, w: G::S) {
+        self.points.push((x, y, w));
+    }
+
+    ///
+    /// x 座標が `x_range`、y 座標が `y_range` に含まれる点の重みの和を求めるクエリを追加する
+    ///
+    /// クエリの番号を返す
+    ///
+    pub fn add_query<R, Q>(&mut self, x_range: R, y_range: Q) -> usize
+    where
+        R: RangeBounds<T>,
+        Q: RangeBounds<T>,
+    {
+        self.queries.push((
+            (x_range.start_bound().cloned(), x_range.end_bound().cloned()),
+            (y_range.start_bound().cloned(), y_range.end_bound().cloned()),
+        ));
+
+        self.queries.len() - 1
+    }
+
+    ///
+    /// 各クエリの答え（追加した順）
+    ///
+    pub fn solve(&self) -> Vec<G::S> {
+        let mut points = self.points.iter().collect::<Vec<_>>();
+        points.sort_by_key(|&&(x, _, _)| x);
+
+        let ys = CoordinateCompression::new(points.iter().map(|&&(_, y, _)| y));
+
+        // x 座標が小さい方から何個の点を使うか
+        let count_less = |x: &T| points.partition_point(|&&(px, _, _)| px < *x);
+        let count_less_equal = |x: &T| points.partition_point(|&&(px, _, _)| px <= *x);
+
+        // (使う点の数, クエリの番号, 足すなら true)
+        let mut events = Vec::with_capacity(self.queries.len() * 2);
+
+        for (i, ((start, end), _)) in self.queries.iter().enumerate() {
+            let l = match start {
+                Bound::Included(x) => count_less(x),
+                Bound::Excluded(x) => count_less_equal(x),
+                Bound::Unbounded => 0,
+            };
+
+            let r = match end {
+                Bound::Included(x) => count_less_equal(x),
+                Bound::Excluded(x) => count_less(x),
+                Bound::Unbounded => points.len(),
+            };
+
+            if l < r {
+                events.push((r, i, true));
+                events.push((l, i, false));
+            }
+        }
+
+        events.sort_by_key(|&(count, _, _)| count);
+
+        let mut result = vec![G::identity(); self.queries.len()];
+        let mut tree = GenericBinaryIndexedTree::<G>::new(ys.len());
+        let mut added = 0;
+
+        for (count, i, positive) in events {
+            while added < count {
+                let (_, y, w) = points[added];
+
+                tree.add(ys.index_of(y) + 1, w.clone());
+
+                added += 1;
+            }
+
+            let (_, (start, end)) = &self.queries[i];
+
+            let lower = match start {
+                Bound::Included(y) => ys.lower_bound(y),
+                Bound::Excluded(y) => ys.upper_bound(y),
+                Bound::Unbounded => 0,
+            };
+
+            let upper = match end {
+                Bound::Included(y) => ys.upper_bound(y),
+                Bound::Excluded(y) => ys.lower_bound(y),
+                Bound::Unbounded => ys.len(),
+            };
+
+            if lower >= upper {
+                continue;
+            }
+
+            let s = tree.range_sum(lower..upper);
+
+            result[i] = if positive {
+                G::binary_operation(&result[i], &s)
+            } else {
+                G::binary_operation(&result[i], &G::inverse(&s))
+            };
+        }
+
+        result
+    }
+}
+
+impl<G: Group, T: Ord + Copy> Default for OfflineRectangleSum<G, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///
+/// 各クエリ点 (a, b) について x <= a かつ y <= b となる点 (x, y) の個数
+///
+/// O((N + Q) log N)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::dominance_count;
+///
+/// let points = vec![(1, 1), (2, 3), (3, 2), (2, 2)];
+///
+/// assert_eq!(dominance_count(&points, &[(2, 2), (3, 3), (0, 5)]), vec![2, 4, 0]);
+/// ```
+///
+pub fn dominance_count<T: Ord + Copy>(points: &[(T, T)], queries: &[(T, T)]) -> Vec<usize> {
+    let mut solver = OfflineRectangleSum::<Additive<i64>, T>::new();
+
+    for &(x, y) in points.iter() {
+        solver.add_point(x, y, 1);
+    }
+
+    for &(a, b) in queries.iter() {
+        solver.add_query(..=a, ..=b);
+    }
+
+    solver.solve().into_iter().map(|c| c as usize).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dominance_count, OfflineRectangleSum};
+    use crate::Additive;
+    use std::ops::{Bound, RangeBounds};
+
+    #[test]
+    fn test_offline_rectangle_sum() {
+        let points = vec![
+            (0, 0, 1),
+            (2, 3, 10),
+            (2, 3, -4),
+            (5, 1, 100),
+            (-3, 4, 1000),
+            (5, 5, 10000),
+            (1, -2, 7),
+        ];
+
+        let mut solver = OfflineRectangleSum::<Additive<i64>, i32>::new();
+
+        for &(x, y, w) in points.iter() {
+            solver.add_point(x, y, w);
+        }
+
+        let bounds = [
+            Bound::Unbounded,
+            Bound::Included(-3),
+            Bound::Excluded(0),
+            Bound::Included(2),
+            Bound::Excluded(5),
+            Bound::Included(5),
+            Bound::Included(9),
+        ];
+
+        // 全ての (始点, 終点) の組をクエリにする
+        let mut queries = Vec::new();
+
+        for &x_start in bounds.iter() {
+            for &x_end in bounds.iter() {
+                for &(y_start, y_end) in [
+                    (Bound::Unbounded, Bound::Unbounded),
+                    (Bound::Included(1), Bound::Excluded(4)),
+                    (Bound::Excluded(-2), Bound::Included(3)),
+                    (Bound::Included(4), Bound::Included(0)),
+                ]
+                .iter()
+                {
+                    queries.push(((x_start, x_end), (y_start, y_end)));
+                }
+            }
+        }
+
+        for (i, &(x_range, y_range)) in queries.iter().enumerate() {
+            assert_eq!(solver.add_query(x_range, y_range), i);
+        }
+
+        let expected = queries
+            .iter()
+            .map(|(x_range, y_range)| {
+                points
+                    .iter()
+                    .filter(|&(x, y, _)| x_range.contains(x) && y_range.contains(y))
+                    .map(|&(_, _, w)| w)
+                    .sum::<i64>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(solver.solve(), expected);
+    }
+
+    #[test]
+    fn test_dominance_count() {
+        let points = vec![(3, 1), (1, 4), (1, 4), (5, 9), (2, 6)];
+        let queries = vec![(0, 0), (1, 4), (3, 6), (10, 10), (5, 1)];
+
+        let expected = queries
+            .iter()
+            .map(|&(a, b)| points.iter().filter(|&&(x, y)| x <= a && y <= b).count())
+            .collect::<Vec<_>>();
+
+        assert_eq!(dominance_count(&points, &queries), expected);
+        assert_eq!(dominance_count::<i32>(&[], &[(1, 1)]), vec![0]);
+    }
+}
@@ -0,0 +1,229 @@
+///
+/// BIT による順序付き多重集合
+///
+/// 値の範囲 [0, n) を固定し、値ごとの個数を BIT で持つ。
+/// 各操作 O(log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::OrderedMultiset;
+///
+/// let mut set = OrderedMultiset::new(10);
+///
+/// set.insert(3);
+/// set.insert(7);
+/// set.insert(3);
+///
+/// assert_eq!(set.count_less(5), 2);
+/// assert_eq!(set.kth(2), Some(7));
+/// assert_eq!(set.prev(6), Some(3));
+/// assert_eq!(set.next(4), Some(7));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct OrderedMultiset {
+    n: usize,
+    /// 1-indexed
+    bit: Vec<usize>,
+    /// 要素数
+    len: usize,
+}
+
+impl OrderedMultiset {
+    ///
+    /// 値の範囲が [0, n) の空の多重集合
+    ///
+    pub fn new(n: usize) -> OrderedMultiset {
+        OrderedMultiset {
+            n,
+            bit: vec![0; n + 1],
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, x: usize) {
+        assert!(x < self.n, "x is out of range");
+
+        let mut index = x + 1;
+
+        while index <= self.n {
+            self.bit[index] += 1;
+
+            index += index & index.wrapping_neg();
+        }
+
+        self.len += 1;
+    }
+
+    ///
+    /// x を 1 つ削除する
+    ///
+    /// x がなければ false を返す
+    ///
+    pub fn remove(&mut self, x: usize) -> bool {
+        if self.count(x) == 0 {
+            return false;
+        }
+
+        let mut index = x + 1;
+
+        while index <= self.n {
+            self.bit[index] -= 1;
+
+            index += index & index.wrapping_neg();
+        }
+
+        self.len -= 1;
+
+        true
+    }
+
+    ///
+    /// x の個数
+    ///
+    pub fn count(&self, x: usize) -> usize {
+        if x >= self.n {
+            return 0;
+        }
+
+        self.count_less(x + 1) - self.count_less(x)
+    }
+
+    ///
+    /// x 未満の要素の個数
+    ///
+    pub fn count_less(&self, x: usize) -> usize {
+        let mut s = 0;
+
+        let mut index = x.min(self.n);
+
+        while index > 0 {
+            s += self.bit[index];
+
+            index -= index & index.wrapping_neg();
+        }
+
+        s
+    }
+
+    ///
+    /// 小さい方から k 番目（0-indexed）の要素
+    ///
+    /// BIT 上の二分探索で O(log n)
+    ///
+    pub fn kth(&self, k: usize) -> Option<usize> {
+        if k >= self.len {
+            return None;
+        }
+
+        let mut step = 1;
+        while step * 2 <= self.n {
+            step *= 2;
+        }
+
+        // 個数の累積が k + 1 未満となる最大の位置
+        let mut position = 0;
+        let mut rest = k + 1;
+
+        while step > 0 {
+            if position + step <= self.n && self.bit[position + step] < rest {
+                position += step;
+                rest -= self.bit[position];
+            }
+
+            step /= 2;
+        }
+
+        Some(position)
+    }
+
+    ///
+    /// x 以下の最大の要素
+    ///
+    pub fn prev(&self, x: usize) -> Option<usize> {
+        match self.count_less(x.saturating_add(1)) {
+            0 => None,
+            c => self.kth(c - 1),
+        }
+    }
+
+    ///
+    /// x 以上の最小の要素
+    ///
+    pub fn next(&self, x: usize) -> Option<usize> {
+        self.kth(self.count_less(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OrderedMultiset;
+
+    #[test]
+    fn test_ordered_multiset() {
+        let n = 13;
+        let mut set = OrderedMultiset::new(n);
+        let mut expected = Vec::new();
+
+        // (削除なら true, x)
+        let operations = [
+            (false, 5),
+            (false, 3),
+            (false, 5),
+            (false, 12),
+            (false, 0),
+            (true, 7),
+            (true, 5),
+            (false, 8),
+            (false, 3),
+            (true, 0),
+            (true, 0),
+            (false, 12),
+            (true, 3),
+            (false, 6),
+            (true, 12),
+        ];
+
+        for &(remove, x) in operations.iter() {
+            if remove {
+                let position = expected.iter().position(|&v| v == x);
+
+                assert_eq!(set.remove(x), position.is_some());
+
+                if let Some(position) = position {
+                    expected.remove(position);
+                }
+            } else {
+                set.insert(x);
+                expected.push(x);
+                expected.sort();
+            }
+
+            assert_eq!(set.len(), expected.len());
+
+            for x in 0..=n {
+                assert_eq!(
+                    set.count_less(x),
+                    expected.iter().filter(|&&v| v < x).count()
+                );
+                assert_eq!(set.count(x), expected.iter().filter(|&&v| v == x).count());
+                assert_eq!(
+                    set.prev(x),
+                    expected.iter().rev().find(|&&v| v <= x).copied()
+                );
+                assert_eq!(set.next(x), expected.iter().find(|&&v| v >= x).copied());
+            }
+
+            for k in 0..=expected.len() {
+                assert_eq!(set.kth(k), expected.get(k).copied());
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::monoid::{Max, Min, Monoid};
+
+///
+/// 接頭辞の最大値を求める Binary Indexed Tree
+///
+pub type PrefixMaxBinaryIndexedTree<T> = PrefixBinaryIndexedTree<Max<T>>;
+
+///
+/// 接頭辞の最小値を求める Binary Indexed Tree
+///
+pub type PrefixMinBinaryIndexedTree<T> = PrefixBinaryIndexedTree<Min<T>>;
+
+///
+/// 可換モノイド `M` の接頭辞の積を求める Binary Indexed Tree
+///
+/// 逆元がないので、更新は `a[i] = a[i] · x` の形（最大値なら chmax）のみ、
+/// 取得は接頭辞のみ。各操作 O(log n)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::PrefixMaxBinaryIndexedTree;
+///
+/// let mut tree = PrefixMaxBinaryIndexedTree::<i64>::new(5);
+///
+/// tree.update(1, 10);
+/// tree.update(3, 7);
+/// tree.update(1, 4);
+///
+/// assert_eq!(tree.prefix(1), i64::MIN);
+/// assert_eq!(tree.prefix(3), 10);
+/// assert_eq!(tree.get(1), 10);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PrefixBinaryIndexedTree<M: Monoid> {
+    n: usize,
+    /// 1-indexed
+    bit: Vec<M::S>,
+    value: Vec<M::S>,
+}
+
+impl<M: Monoid> PrefixBinaryIndexedTree<M> {
+    pub fn new(n: usize) -> PrefixBinaryIndexedTree<M> {
+        PrefixBinaryIndexedTree {
+            n,
+            bit: vec![M::identity(); n + 1],
+            value: vec![M::identity(); n],
+        }
+    }
+
+    ///
+    /// 0-indexed の i について `a[i] = a[i] · x` とする
+    ///
+    pub fn update(&mut self, i: usize, x: M::S) {
+        assert!(i < self.n, "index out of bounds");
+
+        self.value[i] = M::binary_operation(&self.value[i], &x);
+
+        let mut index = i + 1;
+
+        while index <= self.n {
+            self.bit[index] = M::binary_operation(&self.bit[index], &x);
+
+            index += index & index.wrapping_neg();
+        }
+    }
+
+    ///
+    /// [0, r) の積
+    ///
+    pub fn prefix(&self, r: usize) -> M::S {
+        assert!(r <= self.n, "index out of bounds");
+
+        let mut s = M::identity();
+
+        let mut index = r;
+
+        while index > 0 {
+            s = M::binary_operation(&s, &self.bit[index]);
+
+            index -= index & index.wrapping_neg();
+        }
+
+        s
+    }
+
+    ///
+    /// 0-indexed の i の値
+    ///
+    pub fn get(&self, i: usize) -> M::S {
+        self.value[i].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl<M: Monoid> From<Vec<M::S>> for PrefixBinaryIndexedTree<M> {
+    fn from(value: Vec<M::S>) -> Self {
+        let n = value.len();
+
+        let mut bit = vec![M::identity(); n + 1];
+
+        for i in 1..=n {
+            bit[i] = M::binary_operation(&bit[i], &value[i - 1]);
+
+            let j = i + (i & i.wrapping_neg());
+
+            if j <= n {
+                bit[j] = M::binary_operation(&bit[j], &bit[i]);
+            }
+        }
+
+        PrefixBinaryIndexedTree { n, bit, value }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrefixMaxBinaryIndexedTree, PrefixMinBinaryIndexedTree};
+
+    #[test]
+    fn test_prefix_max_min() {
+        let n = 17;
+        let list = (0..n).map(|i| (i * 7 % 11) as i32 - 5).collect::<Vec<_>>();
+
+        let mut max_list = list.clone();
+        let mut min_list = list.clone();
+        let mut max_tree = PrefixMaxBinaryIndexedTree::from(list.clone());
+        let mut min_tree = PrefixMinBinaryIndexedTree::from(list);
+
+        for &(i, x) in [
+            (3, 20),
+            (3, -20),
+            (0, 0),
+            (16, 7),
+            (8, -6),
+            (8, 6),
+            (1, -5),
+            (12, i32::MAX),
+            (5, i32::MIN),
+        ]
+        .iter()
+        {
+            max_tree.update(i, x);
+            max_list[i] = max_list[i].max(x);
+
+            min_tree.update(i, x);
+            min_list[i] = min_list[i].min(x);
+
+            for r in 0..=n {
+                assert_eq!(
+                    max_tree.prefix(r),
+                    max_list[..r].iter().copied().max().unwrap_or(i32::MIN)
+                );
+                assert_eq!(
+                    min_tree.prefix(r),
+                    min_list[..r].iter().copied().min().unwrap_or(i32::MAX)
+                );
+            }
+
+            for j in 0..n {
+                assert_eq!(max_tree.get(j), max_list[j]);
+                assert_eq!(min_tree.get(j), min_list[j]);
+            }
+        }
+    }
+}
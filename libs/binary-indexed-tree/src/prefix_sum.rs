@@ -0,0 +1,188 @@
+use crate::to_half_open;
+use std::ops::{Add, RangeBounds, Sub};
+
+///
+/// 累積和
+///
+/// 構築 O(n)、区間和 O(1)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::PrefixSum;
+///
+/// let sum = PrefixSum::from(vec![3, 1, 4, 1, 5]);
+///
+/// assert_eq!(sum.sum(1..4), 6);
+/// assert_eq!(sum.sum(..), 14);
+/// assert_eq!(sum.sum(2..2), 0);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PrefixSum<T> {
+    /// prefix[i] : [0, i) の和
+    prefix: Vec<T>,
+}
+
+impl<T> PrefixSum<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    ///
+    /// 0-indexed の区間 `range` の和
+    ///
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (l, r) = to_half_open(range, self.len());
+
+        self.prefix[r].clone() - self.prefix[l].clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> From<Vec<T>> for PrefixSum<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(value: Vec<T>) -> Self {
+        let mut prefix = Vec::with_capacity(value.len() + 1);
+        prefix.push(T::default());
+
+        for x in value {
+            prefix.push(prefix.last().unwrap().clone() + x);
+        }
+
+        PrefixSum { prefix }
+    }
+}
+
+///
+/// 2 次元累積和
+///
+/// 構築 O(hw)、長方形和 O(1)
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::PrefixSum2D;
+///
+/// let sum = PrefixSum2D::from(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+///
+/// assert_eq!(sum.sum(.., ..), 21);
+/// assert_eq!(sum.sum(1.., 1..), 11);
+/// assert_eq!(sum.sum(..1, ..2), 3);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PrefixSum2D<T> {
+    h: usize,
+    w: usize,
+    /// prefix[i][j] : [0, i) × [0, j) の和
+    prefix: Vec<Vec<T>>,
+}
+
+impl<T> PrefixSum2D<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    ///
+    /// 0-indexed の長方形 `rows` × `columns` の和
+    ///
+    pub fn sum<R, C>(&self, rows: R, columns: C) -> T
+    where
+        R: RangeBounds<usize>,
+        C: RangeBounds<usize>,
+    {
+        let (top, bottom) = to_half_open(rows, self.h);
+        let (left, right) = to_half_open(columns, self.w);
+
+        self.prefix[bottom][right].clone() + self.prefix[top][left].clone()
+            - self.prefix[top][right].clone()
+            - self.prefix[bottom][left].clone()
+    }
+
+    pub fn height(&self) -> usize {
+        self.h
+    }
+
+    pub fn width(&self) -> usize {
+        self.w
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for PrefixSum2D<T>
+where
+    T: Clone + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(value: Vec<Vec<T>>) -> Self {
+        let h = value.len();
+        let w = value.first().map_or(0, |row| row.len());
+
+        let mut prefix = vec![vec![T::default(); w + 1]; h + 1];
+
+        for (i, row) in value.into_iter().enumerate() {
+            assert_eq!(row.len(), w, "rows must have the same length");
+
+            for (j, x) in row.into_iter().enumerate() {
+                prefix[i + 1][j + 1] =
+                    prefix[i][j + 1].clone() + prefix[i + 1][j].clone() - prefix[i][j].clone() + x;
+            }
+        }
+
+        PrefixSum2D { h, w, prefix }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PrefixSum, PrefixSum2D};
+    use ac_library::ModInt998244353 as Mint;
+
+    #[test]
+    fn test_prefix_sum() {
+        let list = vec![5_i64, -3, 8, 0, 2, -7];
+        let sum = PrefixSum::from(list.clone());
+
+        assert_eq!(sum.len(), list.len());
+
+        for l in 0..=list.len() {
+            for r in l..=list.len() {
+                assert_eq!(sum.sum(l..r), list[l..r].iter().sum::<i64>());
+            }
+        }
+
+        let sum = PrefixSum::from(vec![Mint::new(998_244_352), Mint::new(5), Mint::new(3)]);
+
+        assert_eq!(sum.sum(..2), Mint::new(4));
+        assert_eq!(sum.sum(1..=2), Mint::new(8));
+    }
+
+    #[test]
+    fn test_prefix_sum_2d() {
+        let (h, w) = (4, 3);
+        let grid = (0..h)
+            .map(|i| (0..w).map(|j| (i * 7 + j * 3) as i64 % 5 - 2).collect())
+            .collect::<Vec<Vec<_>>>();
+
+        let sum = PrefixSum2D::from(grid.clone());
+
+        assert_eq!((sum.height(), sum.width()), (h, w));
+
+        for top in 0..=h {
+            for bottom in top..=h {
+                for left in 0..=w {
+                    for right in left..=w {
+                        let expected = grid[top..bottom]
+                            .iter()
+                            .map(|row| row[left..right].iter().sum::<i64>())
+                            .sum::<i64>();
+
+                        assert_eq!(sum.sum(top..bottom, left..right), expected);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{to_half_open, Additive, GenericBinaryIndexedTree};
+use ac_library::modint::{DynamicModInt, Id, Modulus, StaticModInt};
+use std::ops::RangeBounds;
+
+///
+/// 添字を値の型に変換する
+///
+/// 符号付き整数、浮動小数点数、ac-library の ModInt に実装済み
+///
+pub trait FromUsize {
+    fn from_usize(x: usize) -> Self;
+}
+
+macro_rules! impl_from_usize {
+    ($($t:ty),*) => {
+        $(impl FromUsize for $t {
+            fn from_usize(x: usize) -> Self {
+                x as $t
+            }
+        })*
+    };
+}
+impl_from_usize!(i32, i64, i128, isize, f32, f64);
+
+impl<M: Modulus> FromUsize for StaticModInt<M> {
+    fn from_usize(x: usize) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<I: Id> FromUsize for DynamicModInt<I> {
+    fn from_usize(x: usize) -> Self {
+        Self::new(x)
+    }
+}
+
+///
+/// 区間加算・区間和の Binary Indexed Tree
+///
+/// 2 本の BIT を使い、[0, i) の和を `b_0(i) + b_1(i) * i` で求める
+///
+/// ```
+/// use asakuchi_binary_indexed_tree::RangeAddBinaryIndexedTree;
+///
+/// let mut tree = RangeAddBinaryIndexedTree::<i64>::from(vec![1, 2, 3, 4, 5]);
+///
+/// tree.add(1..4, 10);
+///
+/// assert_eq!(tree.sum(..), 45);
+/// assert_eq!(tree.sum(3..), 19);
+/// assert_eq!(tree.sum(0..2), 13);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RangeAddBinaryIndexedTree<T>
+where
+    T: Clone + Default + std::ops::Add<Output = T> + std::ops::Neg<Output = T>,
+{
+    b_0: GenericBinaryIndexedTree<Additive<T>>,
+    b_1: GenericBinaryIndexedTree<Additive<T>>,
+}
+
+impl<T> RangeAddBinaryIndexedTree<T>
+where
+    T: Clone
+        + Default
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>
+        + std::ops::Neg<Output = T>
+        + FromUsize,
+{
+    pub fn new(n: usize) -> RangeAddBinaryIndexedTree<T> {
+        RangeAddBinaryIndexedTree {
+            b_0: GenericBinaryIndexedTree::new(n),
+            b_1: GenericBinaryIndexedTree::new(n),
+        }
+    }
+
+    ///
+    /// 0-indexed の区間 `range` に x を加える
+    ///
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: T) {
+        let (l, r) = to_half_open(range, self.len());
+
+        if l == r {
+            return;
+        }
+
+        self.b_0.add(l + 1, -(x.clone() * T::from_usize(l)));
+        self.b_1.add(l + 1, x.clone());
+
+        if r < self.len() {
+            self.b_0.add(r + 1, x.clone() * T::from_usize(r));
+            self.b_1.add(r + 1, -x);
+        }
+    }
+
+    ///
+    /// 0-indexed の区間 `range` の和
+    ///
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (l, r) = to_half_open(range, self.len());
+
+        self.prefix_sum(r) - self.prefix_sum(l)
+    }
+
+    pub fn len(&self) -> usize {
+        self.b_0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.b_0.is_empty()
+    }
+
+    ///
+    /// [0, i) の和
+    ///
+    fn prefix_sum(&self, i: usize) -> T {
+        self.b_0.range_sum(..i) + self.b_1.range_sum(..i) * T::from_usize(i)
+    }
+}
+
+///
+/// 0-indexed の値の列から O(n) で構築する
+///
+impl<T> From<Vec<T>> for RangeAddBinaryIndexedTree<T>
+where
+    T: Clone + Default + std::ops::Add<Output = T> + std::ops::Neg<Output = T>,
+{
+    fn from(value: Vec<T>) -> Self {
+        let n = value.len();
+
+        RangeAddBinaryIndexedTree {
+            b_0: GenericBinaryIndexedTree::from(value),
+            b_1: GenericBinaryIndexedTree::new(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RangeAddBinaryIndexedTree;
+    use ac_library::{ModInt, ModInt998244353 as Mint};
+
+    #[test]
+    fn test_range_add() {
+        let mut list = vec![5_i64, -3, 8, 0, 2, 7, -1];
+        let mut tree = RangeAddBinaryIndexedTree::from(list.clone());
+
+        let queries = [(0, 7, 1), (2, 5, -4), (6, 7, 10), (3, 3, 100), (0, 1, 2)];
+
+        for &(l, r, x) in queries.iter() {
+            tree.add(l..r, x);
+
+            for value in list[l..r].iter_mut() {
+                *value += x;
+            }
+
+            for l in 0..=list.len() {
+                for r in l..=list.len() {
+                    assert_eq!(tree.sum(l..r), list[l..r].iter().sum::<i64>());
+                }
+            }
+        }
+
+        let mut tree = RangeAddBinaryIndexedTree::<f64>::new(4);
+
+        tree.add(1..=2, 0.5);
+        assert_eq!(tree.sum(..), 1.0);
+        assert_eq!(tree.len(), 4);
+
+        let mut tree = RangeAddBinaryIndexedTree::from(vec![Mint::new(1); 5]);
+
+        tree.add(1..4, Mint::new(998_244_352));
+        tree.add(3.., Mint::new(10));
+
+        assert_eq!(tree.sum(..), Mint::new(22));
+        assert_eq!(tree.sum(3..4), Mint::new(10));
+
+        ModInt::set_modulus(7);
+        let mut tree = RangeAddBinaryIndexedTree::<ModInt>::new(3);
+
+        tree.add(.., ModInt::new(5));
+
+        assert_eq!(tree.sum(..), ModInt::new(1));
+    }
+}
@@ -0,0 +1,227 @@
+//!
+//! 座標圧縮
+//!
+
+///
+/// 座標圧縮
+///
+/// 値を昇順に 0, 1, 2, ... の添字へ対応させる
+///
+/// ```
+/// use asakuchi_coordinate_compression::CoordinateCompression;
+///
+/// let compression = CoordinateCompression::new(vec![100, -5, 30, 100]);
+///
+/// assert_eq!(compression.len(), 3);
+/// assert_eq!(compression.index_of(&30), 1);
+/// assert_eq!(*compression.value_at(2), 100);
+/// assert_eq!(compression.lower_bound(&0), 1);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoordinateCompression<T: Ord> {
+    /// 昇順、重複なし
+    values: Vec<T>,
+}
+
+impl<T: Ord> CoordinateCompression<T> {
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> CoordinateCompression<T> {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+
+        CoordinateCompression { values }
+    }
+
+    ///
+    /// 複数の列をまとめて圧縮する
+    ///
+    /// ```
+    /// use asakuchi_coordinate_compression::CoordinateCompression;
+    ///
+    /// let a = vec![3, 1];
+    /// let b = vec![2, 3];
+    ///
+    /// let compression = CoordinateCompression::from_iters([a, b]);
+    ///
+    /// assert_eq!(compression.len(), 3);
+    /// ```
+    ///
+    pub fn from_iters<I, J>(lists: I) -> CoordinateCompression<T>
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+    {
+        Self::new(lists.into_iter().flatten())
+    }
+
+    ///
+    /// 異なる値の個数
+    ///
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    ///
+    /// x の添字
+    ///
+    /// 圧縮前の列に x が含まれていなければ panic
+    ///
+    pub fn index_of(&self, x: &T) -> usize {
+        self.values
+            .binary_search(x)
+            .unwrap_or_else(|_| panic!("value is not compressed"))
+    }
+
+    ///
+    /// x の添字（含まれていなければ None）
+    ///
+    pub fn get_index(&self, x: &T) -> Option<usize> {
+        self.values.binary_search(x).ok()
+    }
+
+    ///
+    /// 添字 i の値
+    ///
+    pub fn value_at(&self, i: usize) -> &T {
+        &self.values[i]
+    }
+
+    ///
+    /// x 以上の値の最小の添字（なければ `len()`）
+    ///
+    pub fn lower_bound(&self, x: &T) -> usize {
+        self.values.partition_point(|v| v < x)
+    }
+
+    ///
+    /// x より大きい値の最小の添字（なければ `len()`）
+    ///
+    pub fn upper_bound(&self, x: &T) -> usize {
+        self.values.partition_point(|v| v <= x)
+    }
+
+    ///
+    /// 昇順の値の一覧
+    ///
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Ord> FromIterator<T> for CoordinateCompression<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+///
+/// 2 次元の座標圧縮
+///
+/// x 座標と y 座標をそれぞれ独立に圧縮する
+///
+/// ```
+/// use asakuchi_coordinate_compression::CoordinateCompression2D;
+///
+/// let compression = CoordinateCompression2D::new(vec![(10, -1), (5, 7), (10, 3)]);
+///
+/// assert_eq!(compression.index_of(&(10, 3)), (1, 1));
+/// assert_eq!(compression.x().len(), 2);
+/// assert_eq!(compression.y().len(), 3);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoordinateCompression2D<T: Ord> {
+    x: CoordinateCompression<T>,
+    y: CoordinateCompression<T>,
+}
+
+impl<T: Ord> CoordinateCompression2D<T> {
+    pub fn new<I: IntoIterator<Item = (T, T)>>(points: I) -> CoordinateCompression2D<T> {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+
+        CoordinateCompression2D {
+            x: CoordinateCompression::new(xs),
+            y: CoordinateCompression::new(ys),
+        }
+    }
+
+    pub fn x(&self) -> &CoordinateCompression<T> {
+        &self.x
+    }
+
+    pub fn y(&self) -> &CoordinateCompression<T> {
+        &self.y
+    }
+
+    ///
+    /// 点 (x, y) の添字
+    ///
+    pub fn index_of(&self, (x, y): &(T, T)) -> (usize, usize) {
+        (self.x.index_of(x), self.y.index_of(y))
+    }
+
+    ///
+    /// 添字 (i, j) の点
+    ///
+    pub fn value_at(&self, i: usize, j: usize) -> (&T, &T) {
+        (self.x.value_at(i), self.y.value_at(j))
+    }
+}
+
+impl<T: Ord> FromIterator<(T, T)> for CoordinateCompression2D<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CoordinateCompression, CoordinateCompression2D};
+
+    #[test]
+    fn test_coordinate_compression() {
+        let a = vec![1_000_000_000, 3, -7];
+        let b = vec![3, 42];
+
+        let compression = CoordinateCompression::from_iters([a.iter(), b.iter()]);
+
+        assert_eq!(compression.values(), &[&-7, &3, &42, &1_000_000_000]);
+        assert_eq!(compression.index_of(&&42), 2);
+        assert_eq!(compression.get_index(&&0), None);
+        assert_eq!(**compression.value_at(3), 1_000_000_000);
+
+        assert_eq!(compression.lower_bound(&&-100), 0);
+        assert_eq!(compression.lower_bound(&&3), 1);
+        assert_eq!(compression.upper_bound(&&3), 2);
+        assert_eq!(compression.lower_bound(&&2_000_000_000), 4);
+
+        let compression = "hello".chars().collect::<CoordinateCompression<_>>();
+
+        assert_eq!(compression.values(), &['e', 'h', 'l', 'o']);
+        assert!(!compression.is_empty());
+        assert!(CoordinateCompression::<u8>::new(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_coordinate_compression_2d() {
+        let points = vec![(3, 30), (1, 10), (3, 20), (2, 10)];
+
+        let compression = points
+            .iter()
+            .copied()
+            .collect::<CoordinateCompression2D<_>>();
+
+        let indices = points
+            .iter()
+            .map(|p| compression.index_of(p))
+            .collect::<Vec<_>>();
+
+        assert_eq!(indices, vec![(2, 2), (0, 0), (2, 1), (1, 0)]);
+        assert_eq!(compression.value_at(1, 2), (&2, &30));
+    }
+}
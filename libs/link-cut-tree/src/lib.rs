@@ -0,0 +1,479 @@
+//!
+//! Link-Cut Tree
+//!
+//! 辺の追加・削除がある森で、連結判定やパス上の集約をオンラインで行う
+//!
+//! 各操作はならし O(log n)
+//!
+
+///
+/// モノイド
+///
+/// 二項演算は可換でなくてもよい（パスの向きを考慮して集約する）
+///
+pub trait Monoid {
+    ///
+    /// モノイドの型
+    ///
+    type S: Clone;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S;
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+const NIL: usize = usize::MAX;
+
+///
+/// Link-Cut Tree
+///
+/// ```
+/// use asakuchi_link_cut_tree::{LinkCutTree, Monoid};
+///
+/// struct Sum;
+///
+/// impl Monoid for Sum {
+///     type S = i64;
+///
+///     fn identity() -> i64 {
+///         0
+///     }
+///
+///     fn binary_operation(a: &i64, b: &i64) -> i64 {
+///         a + b
+///     }
+/// }
+///
+/// let mut tree = LinkCutTree::<Sum>::from(vec![1, 2, 3, 4]);
+///
+/// tree.link(0, 1);
+/// tree.link(1, 2);
+/// tree.link(2, 3);
+/// assert_eq!(tree.prod(0, 3), 10);
+///
+/// tree.cut(1, 2);
+/// assert!(!tree.connected(0, 3));
+/// assert_eq!(tree.prod(2, 3), 7);
+/// ```
+///
+#[derive(Debug)]
+pub struct LinkCutTree<M: Monoid> {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    /// splay 木の親、または splay 木の根なら path-parent
+    parent: Vec<usize>,
+    /// 左右反転の遅延フラグ
+    reversed: Vec<bool>,
+    value: Vec<M::S>,
+    /// splay 木の部分木の集約値（左から右）
+    sum: Vec<M::S>,
+    /// splay 木の部分木の集約値（右から左）
+    reversed_sum: Vec<M::S>,
+    /// splay で遅延フラグを解消する頂点を積む作業領域
+    path: Vec<usize>,
+}
+
+impl<M: Monoid> LinkCutTree<M> {
+    ///
+    /// 全ての頂点を単位元で初期化
+    ///
+    pub fn new(n: usize) -> LinkCutTree<M> {
+        Self::from(vec![M::identity(); n])
+    }
+
+    ///
+    /// 辺 (u, v) を追加する
+    ///
+    /// 既に連結なら何もせず false を返す
+    ///
+    pub fn link(&mut self, u: usize, v: usize) -> bool {
+        if self.connected(u, v) {
+            return false;
+        }
+
+        self.evert(u);
+        self.expose(v);
+
+        self.parent[u] = v;
+
+        true
+    }
+
+    ///
+    /// 辺 (u, v) を削除する
+    ///
+    /// 辺がなければ何もせず false を返す
+    ///
+    pub fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v {
+            return false;
+        }
+
+        self.evert(u);
+        self.expose(v);
+
+        // u - v が辺なら、v の左の部分木は u のみ
+        self.push(u);
+
+        if self.left[v] != u || self.right[u] != NIL {
+            return false;
+        }
+
+        self.left[v] = NIL;
+        self.parent[u] = NIL;
+        self.update(v);
+
+        true
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    ///
+    /// u が属する木の根
+    ///
+    pub fn root(&mut self, u: usize) -> usize {
+        self.expose(u);
+
+        let mut x = u;
+
+        loop {
+            self.push(x);
+
+            if self.left[x] == NIL {
+                break;
+            }
+
+            x = self.left[x];
+        }
+
+        self.splay(x);
+
+        x
+    }
+
+    ///
+    /// u を木の根にする
+    ///
+    pub fn evert(&mut self, u: usize) {
+        self.expose(u);
+        self.toggle(u);
+        self.push(u);
+    }
+
+    ///
+    /// 現在の根に対する u と v の最小共通祖先
+    ///
+    /// 連結でなければ None
+    ///
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) {
+            return None;
+        }
+
+        self.expose(u);
+
+        Some(self.expose(v))
+    }
+
+    ///
+    /// u から v へのパス上の値を順に集約する
+    ///
+    /// 連結でなければ panic
+    ///
+    pub fn prod(&mut self, u: usize, v: usize) -> M::S {
+        assert!(self.connected(u, v), "u and v are not connected");
+
+        self.evert(u);
+        self.expose(v);
+
+        self.sum[v].clone()
+    }
+
+    pub fn get(&self, u: usize) -> M::S {
+        self.value[u].clone()
+    }
+
+    pub fn set(&mut self, u: usize, value: M::S) {
+        self.expose(u);
+
+        self.value[u] = value;
+        self.update(u);
+    }
+
+    ///
+    /// 根から u までのパスを 1 つの splay 木にし、u をその根にする
+    ///
+    /// 最後に path-parent を辿った頂点を返す（lca に使う）
+    ///
+    fn expose(&mut self, u: usize) -> usize {
+        let mut last = NIL;
+        let mut current = u;
+
+        while current != NIL {
+            self.splay(current);
+
+            self.right[current] = last;
+            self.update(current);
+
+            last = current;
+            current = self.parent[current];
+        }
+
+        self.splay(u);
+
+        last
+    }
+
+    fn is_root(&self, x: usize) -> bool {
+        let p = self.parent[x];
+
+        p == NIL || (self.left[p] != x && self.right[p] != x)
+    }
+
+    fn toggle(&mut self, x: usize) {
+        if x == NIL {
+            return;
+        }
+
+        std::mem::swap(&mut self.left[x], &mut self.right[x]);
+        std::mem::swap(&mut self.sum[x], &mut self.reversed_sum[x]);
+        self.reversed[x] ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.reversed[x] {
+            self.toggle(self.left[x]);
+            self.toggle(self.right[x]);
+            self.reversed[x] = false;
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let (l, r) = (self.left[x], self.right[x]);
+
+        let mut sum = self.value[x].clone();
+        let mut reversed_sum = self.value[x].clone();
+
+        if l != NIL {
+            sum = M::binary_operation(&self.sum[l], &sum);
+            reversed_sum = M::binary_operation(&reversed_sum, &self.reversed_sum[l]);
+        }
+
+        if r != NIL {
+            sum = M::binary_operation(&sum, &self.sum[r]);
+            reversed_sum = M::binary_operation(&self.reversed_sum[r], &reversed_sum);
+        }
+
+        self.sum[x] = sum;
+        self.reversed_sum[x] = reversed_sum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let p = self.parent[x];
+        let g = self.parent[p];
+
+        if self.left[p] == x {
+            let b = self.right[x];
+
+            self.left[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.right[x] = p;
+        } else {
+            let b = self.left[x];
+
+            self.right[p] = b;
+            if b != NIL {
+                self.parent[b] = p;
+            }
+            self.left[x] = p;
+        }
+
+        self.parent[p] = x;
+        self.parent[x] = g;
+
+        if g != NIL {
+            if self.left[g] == p {
+                self.left[g] = x;
+            } else if self.right[g] == p {
+                self.right[g] = x;
+            }
+        }
+
+        self.update(p);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // 上から順に遅延フラグを解消
+        self.path.push(x);
+        let mut current = x;
+
+        while !self.is_root(current) {
+            current = self.parent[current];
+            self.path.push(current);
+        }
+
+        while let Some(y) = self.path.pop() {
+            self.push(y);
+        }
+
+        while !self.is_root(x) {
+            let p = self.parent[x];
+
+            if !self.is_root(p) {
+                let g = self.parent[p];
+
+                if (self.left[g] == p) == (self.left[p] == x) {
+                    // zig-zig
+                    self.rotate(p);
+                } else {
+                    // zig-zag
+                    self.rotate(x);
+                }
+            }
+
+            self.rotate(x);
+        }
+    }
+}
+
+impl<M: Monoid> From<Vec<M::S>> for LinkCutTree<M> {
+    fn from(value: Vec<M::S>) -> Self {
+        let n = value.len();
+
+        LinkCutTree {
+            left: vec![NIL; n],
+            right: vec![NIL; n],
+            parent: vec![NIL; n],
+            reversed: vec![false; n],
+            sum: value.clone(),
+            reversed_sum: value.clone(),
+            value,
+            path: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkCutTree, Monoid};
+
+    /// 文字列の連結（非可換）
+    struct Concat;
+
+    impl Monoid for Concat {
+        type S = String;
+
+        fn identity() -> Self::S {
+            String::new()
+        }
+
+        fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+            format!("{}{}", a, b)
+        }
+    }
+
+    #[test]
+    fn test_link_cut_tree() {
+        let values: Vec<_> = "abcdef".chars().map(|c| c.to_string()).collect();
+        let mut tree = LinkCutTree::<Concat>::from(values);
+
+        assert!(tree.link(0, 1));
+        assert!(tree.link(1, 2));
+        assert!(tree.link(1, 3));
+        assert!(tree.link(3, 4));
+        assert!(!tree.link(4, 2));
+
+        assert_eq!(tree.prod(2, 4), "cbde");
+        assert_eq!(tree.prod(4, 2), "edbc");
+        assert_eq!(tree.prod(0, 0), "a");
+        assert!(!tree.connected(0, 5));
+
+        tree.evert(0);
+        assert_eq!(tree.lca(2, 4), Some(1));
+        assert_eq!(tree.lca(3, 4), Some(3));
+        assert_eq!(tree.lca(0, 5), None);
+        assert_eq!(tree.root(4), 0);
+
+        tree.evert(4);
+        assert_eq!(tree.lca(0, 2), Some(1));
+        assert_eq!(tree.root(0), 4);
+
+        assert!(!tree.cut(0, 2));
+        assert!(tree.cut(3, 1));
+        assert!(!tree.connected(0, 4));
+        assert!(tree.connected(0, 2));
+
+        tree.set(1, "X".to_string());
+        assert_eq!(tree.get(1), "X");
+        assert_eq!(tree.prod(0, 2), "aXc");
+
+        assert!(tree.link(5, 4));
+        assert!(tree.link(2, 5));
+        assert_eq!(tree.prod(3, 0), "defcXa");
+    }
+
+    #[test]
+    fn test_path_sum() {
+        struct Sum;
+
+        impl Monoid for Sum {
+            type S = u64;
+
+            fn identity() -> Self::S {
+                0
+            }
+
+            fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+                a + b
+            }
+        }
+
+        let mut tree = LinkCutTree::<Sum>::new(8);
+
+        for u in 0..8 {
+            tree.set(u, 1 << u);
+        }
+
+        // 0 - 1 - 2 - 3 - 4 - 5 - 6 - 7 の列
+        for u in 0..7 {
+            assert!(tree.link(u, u + 1));
+        }
+
+        assert_eq!(tree.prod(0, 7), 255);
+        assert_eq!(tree.prod(5, 2), 0b111100);
+
+        tree.evert(3);
+        assert_eq!(tree.root(7), 3);
+        assert_eq!(tree.lca(0, 7), Some(3));
+        assert_eq!(tree.lca(6, 7), Some(6));
+
+        // 6 - 7 を切って 7 を 0 につなぐ
+        assert!(tree.cut(7, 6));
+        assert!(!tree.cut(7, 6));
+        assert!(tree.link(7, 0));
+        assert!(!tree.link(7, 6));
+
+        assert_eq!(tree.prod(6, 7), 0b11111111);
+        assert_eq!(tree.prod(7, 1), 0b10000011);
+
+        tree.set(0, 100);
+        assert_eq!(tree.prod(7, 1), 100 + 128 + 2);
+        assert_eq!(tree.get(0), 100);
+
+        assert!(tree.cut(2, 3));
+        assert!(tree.connected(7, 2));
+        assert!(!tree.connected(7, 3));
+        assert_eq!(tree.prod(3, 6), 8 + 16 + 32 + 64);
+    }
+}
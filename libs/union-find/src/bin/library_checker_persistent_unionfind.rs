@@ -0,0 +1,81 @@
+// verification-helper: PROBLEM https://judge.yosupo.jp/problem/persistent_unionfind
+
+use asakuchi_union_find::PersistentUnionFind;
+
+fn main() {
+    let (n, q) = input_tuple_2();
+    let t_k_u_v = input_tuple_4_vec::<isize>(q);
+
+    // versions[k + 1] : バージョン k
+    let mut versions = Vec::with_capacity(q + 1);
+    versions.push(PersistentUnionFind::new(n));
+
+    for (t, k, u, v) in t_k_u_v {
+        let set = &versions[(k + 1) as usize];
+
+        if t == 0 {
+            let next = set.union(u as usize, v as usize);
+            versions.push(next);
+        } else {
+            println!(
+                "{}",
+                if set.equiv(u as usize, v as usize) {
+                    1
+                } else {
+                    0
+                }
+            );
+
+            let current = set.clone();
+            versions.push(current);
+        }
+    }
+}
+
+pub fn input_tuple_2<T>() -> (T, T)
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    let stdin = std::io::stdin();
+
+    let mut buf = String::new();
+    stdin.read_line(&mut buf).unwrap();
+    buf = buf.trim_end().to_owned();
+
+    let mut iter = buf.split_whitespace();
+
+    let n = iter.next().unwrap().parse().unwrap();
+    let m = iter.next().unwrap().parse().unwrap();
+
+    (n, m)
+}
+
+pub fn input_tuple_4_vec<T>(n: usize) -> Vec<(T, T, T, T)>
+where
+    T: std::str::FromStr,
+    <T as std::str::FromStr>::Err: std::fmt::Debug,
+{
+    // タプルのベクタ
+
+    let stdin = std::io::stdin();
+
+    let mut t_k_u_v = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let mut buf = String::new();
+        stdin.read_line(&mut buf).unwrap();
+        buf = buf.trim_end().to_owned();
+
+        let mut iter = buf.split_whitespace();
+
+        let t = iter.next().unwrap().parse().unwrap();
+        let k = iter.next().unwrap().parse().unwrap();
+        let u = iter.next().unwrap().parse().unwrap();
+        let v = iter.next().unwrap().parse().unwrap();
+
+        t_k_u_v.push((t, k, u, v));
+    }
+
+    t_k_u_v
+}
@@ -1,17 +1,57 @@
+mod group;
+mod mergeable_union_find;
+mod minimum_spanning_tree;
+mod monoid;
+mod monoid_union_find;
+mod offline_dynamic_connectivity;
+mod partially_persistent_union_find;
+mod persistent_union_find;
+mod rollback_union_find;
+
+pub use group::{Additive, Group, Xor};
+pub use mergeable_union_find::{Mergeable, MergeableUnionFind};
+pub use minimum_spanning_tree::{
+    boruvka, kruskal, second_minimum_spanning_forest, MinimumSpanningForest,
+};
+pub use monoid::{Bounded, Max, Min, Monoid};
+pub use monoid_union_find::MonoidUnionFind;
+pub use offline_dynamic_connectivity::{ConnectivityAnswer, OfflineDynamicConnectivity};
+pub use partially_persistent_union_find::PartiallyPersistentUnionFind;
+pub use persistent_union_find::PersistentUnionFind;
+pub use rollback_union_find::RollbackUnionFind;
+
+///
+/// 重みが整数の UnionFind
+///
+pub type UnionFind = WeightedUnionFind<Additive<isize>>;
+
+///
+/// 重みつき UnionFind
+///
+/// 重み（ポテンシャル）は群 `G` の元
+///
+/// 非可換群の場合、`diff(x, y)` は `weight(x)⁻¹ · weight(y)`
+///
 #[derive(Debug)]
-pub struct UnionFind {
+pub struct WeightedUnionFind<G: Group> {
     rank: Vec<usize>,
     parent: Vec<usize>,
-    /// 重み
-    diff_weight: Vec<isize>,
+    /// 根のみ有効なグループの要素数
+    size: Vec<usize>,
+    /// グループの数
+    count: usize,
+    /// 親からの重み
+    diff_weight: Vec<G::S>,
 }
 
-impl UnionFind {
-    pub fn new(n: usize) -> UnionFind {
-        UnionFind {
+impl<G: Group> WeightedUnionFind<G> {
+    pub fn new(n: usize) -> WeightedUnionFind<G> {
+        WeightedUnionFind {
             rank: vec![0; n],
             parent: (0..n).collect::<Vec<_>>(),
-            diff_weight: vec![0; n],
+            size: vec![1; n],
+            count: n,
+            diff_weight: vec![G::identity(); n],
         }
     }
 
@@ -19,7 +59,8 @@ impl UnionFind {
         if x != self.parent[x] {
             let r = self.find(self.parent[x]);
 
-            self.diff_weight[x] += self.diff_weight[self.parent[x]];
+            self.diff_weight[x] =
+                G::binary_operation(&self.diff_weight[self.parent[x]], &self.diff_weight[x]);
 
             self.parent[x] = r;
         }
@@ -27,20 +68,42 @@ impl UnionFind {
         self.parent[x]
     }
 
-    pub fn union(&mut self, x: usize, y: usize) {
-        self.union_weight(x, y, 0);
+    ///
+    /// 併合した場合は true を返す
+    ///
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        if self.equiv(x, y) {
+            return false;
+        }
+
+        self.union_weight(x, y, G::identity())
     }
 
-    pub fn union_weight(&mut self, x: usize, y: usize, w: isize) {
-        let mut w = w + self.weight(x) - self.weight(y);
+    ///
+    /// `diff(x, y) = w` となるように併合する
+    ///
+    /// 既に同じグループの場合は何もせず、
+    /// 既存の制約と矛盾しなければ true、矛盾すれば false を返す
+    ///
+    pub fn union_weight(&mut self, x: usize, y: usize, w: G::S) -> bool {
+        let weight_x = self.weight(x);
+        let weight_y = self.weight(y);
+
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            return G::binary_operation(&G::inverse(&weight_x), &weight_y) == w;
+        }
 
-        let mut x = self.find(self.parent[x]);
-        let mut y = self.find(self.parent[y]);
+        // weight(x) · w · weight(y)⁻¹
+        let mut w =
+            G::binary_operation(&G::binary_operation(&weight_x, &w), &G::inverse(&weight_y));
 
         // rank[a] > rank[b] となるように swap
         if self.rank[x] < self.rank[y] {
             std::mem::swap(&mut x, &mut y);
-            w = -w;
+            w = G::inverse(&w);
         }
 
         if self.rank[x] == self.rank[y] {
@@ -48,18 +111,25 @@ impl UnionFind {
         }
 
         self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.count -= 1;
 
         self.diff_weight[y] = w;
+
+        true
     }
 
     pub fn equiv(&mut self, x: usize, y: usize) -> bool {
         self.find(x) == self.find(y)
     }
 
-    pub fn weight(&mut self, x: usize) -> isize {
+    ///
+    /// 根からの重み
+    ///
+    pub fn weight(&mut self, x: usize) -> G::S {
         self.find(x);
 
-        self.diff_weight[x]
+        self.diff_weight[x].clone()
     }
 
     ///
@@ -67,14 +137,60 @@ impl UnionFind {
     ///
     /// （重みつき UnionFind）
     ///
-    pub fn diff(&mut self, x: usize, y: usize) -> isize {
-        self.weight(y) - self.weight(x)
+    pub fn diff(&mut self, x: usize, y: usize) -> G::S {
+        let weight_x = self.weight(x);
+        let weight_y = self.weight(y);
+
+        G::binary_operation(&G::inverse(&weight_x), &weight_y)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+
+        self.size[root]
+    }
+
+    ///
+    /// グループの数
+    ///
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// グループごとの要素の一覧
+    ///
+    /// グループは最小の要素の昇順、要素は昇順に並ぶ
+    ///
+    /// O(n α(n))
+    ///
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let n = self.parent.len();
+
+        let mut index = vec![usize::MAX; n];
+        let mut groups = Vec::with_capacity(self.count);
+
+        for x in 0..n {
+            let root = self.find(x);
+
+            if index[root] == usize::MAX {
+                index[root] = groups.len();
+                groups.push(Vec::with_capacity(self.size[root]));
+            }
+
+            groups[index[root]].push(x);
+        }
+
+        groups
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::UnionFind;
+    use super::{Additive, Group, UnionFind, WeightedUnionFind, Xor};
 
     #[test]
     fn test_disjoint_set() {
@@ -94,4 +210,125 @@ mod tests {
         assert_eq!(set.find(0), set.find(1));
         assert_eq!(set.find(1), set.find(2));
     }
+
+    #[test]
+    fn test_size_count_groups() {
+        let mut set = UnionFind::new(6);
+
+        assert_eq!(set.count(), 6);
+        assert_eq!(set.size(0), 1);
+
+        set.union(0, 3);
+        set.union(4, 3);
+        set.union(1, 5);
+        set.union(0, 4);
+
+        assert_eq!(set.count(), 3);
+        assert_eq!(set.size(0), 3);
+        assert_eq!(set.size(4), 3);
+        assert_eq!(set.size(5), 2);
+        assert_eq!(set.size(2), 1);
+
+        assert_eq!(set.groups(), vec![vec![0, 3, 4], vec![1, 5], vec![2]]);
+
+        set.union(2, 5);
+
+        let groups = set.groups();
+
+        assert_eq!(groups.len(), set.count());
+
+        for group in groups {
+            for &x in group.iter() {
+                assert_eq!(set.size(x), group.len());
+                assert!(set.equiv(x, group[0]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_weight_contradiction() {
+        let mut set = UnionFind::new(4);
+
+        assert!(set.union_weight(0, 1, 3));
+        assert!(set.union_weight(1, 2, 4));
+        assert_eq!(set.diff(0, 2), 7);
+        assert_eq!(set.diff(2, 0), -7);
+
+        assert!(set.union_weight(0, 2, 7));
+        assert!(!set.union_weight(2, 0, 7));
+        assert_eq!(set.diff(0, 2), 7);
+
+        assert!(!set.union(3, 3));
+        assert!(set.union(3, 0));
+        assert_eq!(set.diff(3, 2), 7);
+    }
+
+    #[test]
+    fn test_xor_parity() {
+        // 二部グラフ判定
+        let mut set = WeightedUnionFind::<Xor<bool>>::new(4);
+
+        assert!(set.union_weight(0, 1, true));
+        assert!(set.union_weight(1, 2, true));
+        assert!(!set.diff(0, 2));
+        assert!(set.union_weight(2, 3, true));
+        assert!(!set.union_weight(3, 0, false));
+
+        let mut set = WeightedUnionFind::<Xor<u32>>::new(3);
+
+        set.union_weight(0, 1, 0b101);
+        set.union_weight(2, 1, 0b011);
+        assert_eq!(set.diff(0, 2), 0b110);
+    }
+
+    #[test]
+    fn test_non_commutative() {
+        // 回転（90度単位）と平行移動の組
+        // (r, (x, y)) : 点 p を rot(r) * p + (x, y) に移す
+        struct Motion;
+
+        fn rotate(r: u8, (x, y): (i64, i64)) -> (i64, i64) {
+            (0..r).fold((x, y), |(x, y), _| (-y, x))
+        }
+
+        impl Group for Motion {
+            type S = (u8, (i64, i64));
+
+            fn identity() -> Self::S {
+                (0, (0, 0))
+            }
+
+            // a の後に b を適用
+            fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+                let (x, y) = rotate(b.0, a.1);
+                ((a.0 + b.0) % 4, (x + b.1 .0, y + b.1 .1))
+            }
+
+            fn inverse(a: &Self::S) -> Self::S {
+                let r = (4 - a.0) % 4;
+                let (x, y) = rotate(r, a.1);
+                (r, (-x, -y))
+            }
+        }
+
+        let mut set = WeightedUnionFind::<Motion>::new(3);
+
+        let a = (1, (2, 0));
+        let b = (2, (0, 5));
+
+        assert!(set.union_weight(0, 1, a));
+        assert!(set.union_weight(2, 1, b));
+
+        let expected = Motion::binary_operation(&a, &Motion::inverse(&b));
+
+        assert_eq!(set.diff(0, 2), expected);
+        assert_eq!(set.diff(2, 0), Motion::inverse(&expected));
+        assert!(set.union_weight(0, 2, expected));
+        assert!(!set.union_weight(2, 0, expected));
+
+        let mut set = WeightedUnionFind::<Additive<f64>>::new(2);
+
+        set.union_weight(0, 1, 0.5);
+        assert_eq!(set.diff(1, 0), -0.5);
+    }
 }
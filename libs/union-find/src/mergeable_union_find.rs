@@ -0,0 +1,292 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+
+///
+/// [`MergeableUnionFind`] のグループごとに持つコンテナ
+///
+pub trait Mergeable: Default {
+    ///
+    /// 要素数（小さい方を大きい方へ併合する基準）
+    ///
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// other の要素を全て self に移す
+    ///
+    fn merge(&mut self, other: Self);
+}
+
+impl<T: Ord> Mergeable for BTreeSet<T> {
+    fn len(&self) -> usize {
+        BTreeSet::len(self)
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+impl<T: Eq + Hash> Mergeable for HashSet<T> {
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+///
+/// 同じキーの値は足し合わせる（個数を数える多重集合など）
+///
+impl<K: Ord, V: std::ops::AddAssign> Mergeable for BTreeMap<K, V> {
+    fn len(&self) -> usize {
+        BTreeMap::len(self)
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (key, value) in other {
+            match self.get_mut(&key) {
+                Some(current) => *current += value,
+                None => {
+                    self.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+///
+/// 同じキーの値は足し合わせる（個数を数える多重集合など）
+///
+impl<K: Eq + Hash, V: std::ops::AddAssign> Mergeable for HashMap<K, V> {
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (key, value) in other {
+            match self.get_mut(&key) {
+                Some(current) => *current += value,
+                None => {
+                    self.insert(key, value);
+                }
+            }
+        }
+    }
+}
+
+///
+/// 順序を持たない要素の列
+///
+/// 併合すると後ろに連結するだけなので、ソート済みの列を渡しても併合後はソートされていない
+///
+impl<T> Mergeable for Vec<T> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn merge(&mut self, mut other: Self) {
+        self.append(&mut other);
+    }
+}
+
+///
+/// グループごとにコンテナを持つ UnionFind
+///
+/// 併合時は要素数の小さいコンテナを大きいコンテナへ移す（マージテク）。
+/// 全体で各要素の移動は O(log n) 回
+///
+/// 順序を保つのは `BTreeSet` と `BTreeMap` のみで、k 番目に小さい値は `iter().nth(k)` で O(k) かかる
+///
+/// ```
+/// use asakuchi_union_find::MergeableUnionFind;
+/// use std::collections::BTreeSet;
+///
+/// // 各頂点の色
+/// let colors = vec![1, 2, 1, 3];
+///
+/// let mut set = MergeableUnionFind::from(
+///     colors.into_iter().map(|c| BTreeSet::from([c])).collect::<Vec<_>>(),
+/// );
+///
+/// set.union(0, 2);
+/// assert_eq!(set.get(0).len(), 1);
+///
+/// set.union(2, 3);
+/// assert_eq!(set.get(0).len(), 2);
+/// assert_eq!(set.get(3).iter().nth(1), Some(&3));
+/// ```
+///
+#[derive(Debug)]
+pub struct MergeableUnionFind<C: Mergeable> {
+    parent: Vec<usize>,
+    /// 根のみ有効なグループの要素数
+    size: Vec<usize>,
+    /// グループの数
+    count: usize,
+    /// 根のみ有効なグループのコンテナ
+    container: Vec<C>,
+}
+
+impl<C: Mergeable> MergeableUnionFind<C> {
+    ///
+    /// 全ての頂点を空のコンテナで初期化
+    ///
+    pub fn new(n: usize) -> MergeableUnionFind<C> {
+        Self::from((0..n).map(|_| C::default()).collect::<Vec<_>>())
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        if x != self.parent[x] {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+
+        self.parent[x]
+    }
+
+    ///
+    /// 併合した場合は true を返す
+    ///
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            return false;
+        }
+
+        // size[x] >= size[y] となるように swap
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.count -= 1;
+
+        // コンテナは大きい方を根に残す
+        if self.container[x].len() < self.container[y].len() {
+            self.container.swap(x, y);
+        }
+
+        let small = std::mem::take(&mut self.container[y]);
+        self.container[x].merge(small);
+
+        true
+    }
+
+    pub fn equiv(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+
+        self.size[root]
+    }
+
+    ///
+    /// グループの数
+    ///
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// x が属するグループのコンテナ
+    ///
+    pub fn get(&mut self, x: usize) -> &C {
+        let root = self.find(x);
+
+        &self.container[root]
+    }
+
+    ///
+    /// x が属するグループのコンテナ
+    ///
+    pub fn get_mut(&mut self, x: usize) -> &mut C {
+        let root = self.find(x);
+
+        &mut self.container[root]
+    }
+}
+
+impl<C: Mergeable> From<Vec<C>> for MergeableUnionFind<C> {
+    fn from(container: Vec<C>) -> Self {
+        let n = container.len();
+
+        MergeableUnionFind {
+            parent: (0..n).collect::<Vec<_>>(),
+            size: vec![1; n],
+            count: n,
+            container,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MergeableUnionFind;
+    use std::collections::{BTreeMap, HashSet};
+
+    #[test]
+    fn test_mergeable() {
+        // 色ごとの個数
+        let colors = [3, 1, 3, 2, 1, 3];
+
+        let mut set = MergeableUnionFind::from(
+            colors
+                .iter()
+                .map(|&c| BTreeMap::from([(c, 1)]))
+                .collect::<Vec<_>>(),
+        );
+
+        set.union(0, 1);
+        set.union(2, 3);
+        set.union(4, 5);
+        set.union(1, 2);
+
+        assert_eq!(set.get(3), &BTreeMap::from([(1, 1), (2, 1), (3, 2)]));
+        assert_eq!(set.get(5), &BTreeMap::from([(1, 1), (3, 1)]));
+
+        set.union(5, 0);
+
+        assert_eq!(set.get(4), &BTreeMap::from([(1, 2), (2, 1), (3, 3)]));
+        assert_eq!(set.size(2), 6);
+        assert_eq!(set.count(), 1);
+    }
+
+    #[test]
+    fn test_new() {
+        let mut set = MergeableUnionFind::<HashSet<char>>::new(4);
+
+        set.get_mut(0).insert('a');
+        set.get_mut(1).insert('b');
+        set.get_mut(1).insert('a');
+        set.union(0, 1);
+        set.union(2, 3);
+
+        assert_eq!(set.get(1).len(), 2);
+        assert!(set.get(3).is_empty());
+
+        set.get_mut(3).insert('c');
+        assert!(set.get(2).contains(&'c'));
+
+        let mut set = MergeableUnionFind::from(vec![vec![5, 1], vec![4], vec![2, 3]]);
+
+        set.union(0, 1);
+        set.union(2, 1);
+
+        let mut values = set.get(0).clone();
+        values.sort();
+        assert_eq!(values, vec![1, 2, 3, 4, 5]);
+    }
+}
@@ -0,0 +1,403 @@
+use crate::UnionFind;
+
+///
+/// 最小全域森
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimumSpanningForest<W> {
+    /// 重みの合計
+    pub weight: W,
+    /// 採用した辺の番号（昇順）
+    pub edges: Vec<usize>,
+    /// forest[v] : (隣接する頂点, 辺の番号)
+    pub forest: Vec<Vec<(usize, usize)>>,
+}
+
+impl<W> MinimumSpanningForest<W>
+where
+    W: Copy + Ord + Default + std::ops::Add<Output = W>,
+{
+    fn from_edges(n: usize, edges: &[(usize, usize, W)], mut chosen: Vec<usize>) -> Self {
+        chosen.sort();
+
+        let mut weight = W::default();
+        let mut forest = vec![Vec::new(); n];
+
+        for &i in chosen.iter() {
+            let (u, v, w) = edges[i];
+
+            weight = weight + w;
+            forest[u].push((v, i));
+            forest[v].push((u, i));
+        }
+
+        MinimumSpanningForest {
+            weight,
+            edges: chosen,
+            forest,
+        }
+    }
+
+    ///
+    /// 全域木か（連結か）
+    ///
+    pub fn is_spanning_tree(&self) -> bool {
+        self.edges.len() + 1 == self.forest.len()
+    }
+}
+
+///
+/// クラスカル法
+///
+/// `edges[i] = (u, v, w)`
+///
+/// 重みが等しい辺は番号が小さい方を優先する
+///
+/// O(m log m)
+///
+/// ```
+/// use asakuchi_union_find::kruskal;
+///
+/// let edges = vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5)];
+///
+/// let mst = kruskal(4, &edges);
+///
+/// assert_eq!(mst.weight, 8);
+/// assert_eq!(mst.edges, vec![1, 2, 3]);
+/// ```
+///
+pub fn kruskal<W>(n: usize, edges: &[(usize, usize, W)]) -> MinimumSpanningForest<W>
+where
+    W: Copy + Ord + Default + std::ops::Add<Output = W>,
+{
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| (edges[i].2, i));
+
+    let mut set = UnionFind::new(n);
+    let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+
+    for i in order {
+        let (u, v, _) = edges[i];
+
+        if set.union(u, v) {
+            chosen.push(i);
+        }
+    }
+
+    MinimumSpanningForest::from_edges(n, edges, chosen)
+}
+
+///
+/// ブルーフカ法
+///
+/// `edges[i] = (u, v, w)`
+///
+/// 重みが等しい辺は番号が小さい方を優先するので、[`kruskal`] と同じ辺を選ぶ
+///
+/// O(m log n)
+///
+pub fn boruvka<W>(n: usize, edges: &[(usize, usize, W)]) -> MinimumSpanningForest<W>
+where
+    W: Copy + Ord + Default + std::ops::Add<Output = W>,
+{
+    let mut set = UnionFind::new(n);
+    let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+
+    loop {
+        // cheapest[root] : グループから出る最小の辺
+        let mut cheapest: Vec<Option<usize>> = vec![None; n];
+
+        for (i, &(u, v, w)) in edges.iter().enumerate() {
+            let root_u = set.find(u);
+            let root_v = set.find(v);
+
+            if root_u == root_v {
+                continue;
+            }
+
+            for root in [root_u, root_v] {
+                match cheapest[root] {
+                    Some(j) if (edges[j].2, j) <= (w, i) => (),
+                    _ => cheapest[root] = Some(i),
+                }
+            }
+        }
+
+        let mut merged = false;
+
+        for i in cheapest.into_iter().flatten() {
+            let (u, v, _) = edges[i];
+
+            if set.union(u, v) {
+                chosen.push(i);
+                merged = true;
+            }
+        }
+
+        if !merged {
+            break;
+        }
+    }
+
+    MinimumSpanningForest::from_edges(n, edges, chosen)
+}
+
+///
+/// 2 番目に小さい全域森
+///
+/// [`kruskal`] で選ばれる最小全域森と 1 辺以上異なるもののうち、重みの合計が最小のもの。
+/// 最小全域森と重みが等しい場合もある
+///
+/// (重みの合計, 追加する辺の番号, 取り除く辺の番号) を返す。
+/// 入れ替えられる辺がなければ None
+///
+/// O(m log n)
+///
+/// ```
+/// use asakuchi_union_find::second_minimum_spanning_forest;
+///
+/// let edges = vec![(0, 1, 3), (1, 2, 1), (0, 2, 2), (2, 3, 5)];
+///
+/// assert_eq!(second_minimum_spanning_forest(4, &edges), Some((9, 0, 2)));
+/// ```
+///
+pub fn second_minimum_spanning_forest<W>(
+    n: usize,
+    edges: &[(usize, usize, W)],
+) -> Option<(W, usize, usize)>
+where
+    W: Copy + Ord + Default + std::ops::Add<Output = W> + std::ops::Sub<Output = W>,
+{
+    let mst = kruskal(n, edges);
+
+    let mut log_n = 1;
+    while 1 << log_n < n {
+        log_n += 1;
+    }
+
+    // parent[k][v] : v から 2^k 上の頂点と、その間で重みが最大の辺
+    let mut parent = vec![vec![(usize::MAX, usize::MAX); n]; log_n];
+    let mut depth = vec![usize::MAX; n];
+
+    for root in 0..n {
+        if depth[root] != usize::MAX {
+            continue;
+        }
+
+        depth[root] = 0;
+        parent[0][root] = (root, usize::MAX);
+
+        let mut stack = vec![root];
+
+        while let Some(v) = stack.pop() {
+            for &(next, i) in mst.forest[v].iter() {
+                if depth[next] == usize::MAX {
+                    depth[next] = depth[v] + 1;
+                    parent[0][next] = (v, i);
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
+    let heavier = |a: usize, b: usize| -> usize {
+        match (a, b) {
+            (usize::MAX, _) => b,
+            (_, usize::MAX) => a,
+            _ if edges[a].2 >= edges[b].2 => a,
+            _ => b,
+        }
+    };
+
+    for k in 0..log_n - 1 {
+        for v in 0..n {
+            let (middle, edge_1) = parent[k][v];
+            let (to, edge_2) = parent[k][middle];
+
+            parent[k + 1][v] = (to, heavier(edge_1, edge_2));
+        }
+    }
+
+    // u - v パス上で重みが最大の辺
+    let path_max = |mut u: usize, mut v: usize| -> usize {
+        let mut result = usize::MAX;
+
+        if depth[u] < depth[v] {
+            std::mem::swap(&mut u, &mut v);
+        }
+
+        for k in (0..log_n).rev() {
+            if depth[u] - depth[v] >= 1 << k {
+                result = heavier(result, parent[k][u].1);
+                u = parent[k][u].0;
+            }
+        }
+
+        if u == v {
+            return result;
+        }
+
+        for k in (0..log_n).rev() {
+            if parent[k][u].0 != parent[k][v].0 {
+                result = heavier(result, parent[k][u].1);
+                result = heavier(result, parent[k][v].1);
+                u = parent[k][u].0;
+                v = parent[k][v].0;
+            }
+        }
+
+        result = heavier(result, parent[0][u].1);
+        heavier(result, parent[0][v].1)
+    };
+
+    let mut in_tree = vec![false; edges.len()];
+    for &i in mst.edges.iter() {
+        in_tree[i] = true;
+    }
+
+    let mut best: Option<(W, usize, usize)> = None;
+
+    for (i, &(u, v, w)) in edges.iter().enumerate() {
+        if in_tree[i] || u == v {
+            continue;
+        }
+
+        let removed = path_max(u, v);
+        let weight = mst.weight - edges[removed].2 + w;
+
+        if best.map_or(true, |(best_weight, _, _)| weight < best_weight) {
+            best = Some((weight, i, removed));
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{boruvka, kruskal, second_minimum_spanning_forest};
+
+    #[test]
+    fn test_minimum_spanning_forest() {
+        let edges = vec![
+            (0, 1, 7),
+            (0, 3, 5),
+            (1, 2, 8),
+            (1, 3, 9),
+            (1, 4, 7),
+            (2, 4, 5),
+            (3, 4, 15),
+            (3, 5, 6),
+            (4, 5, 8),
+            (4, 6, 9),
+            (5, 6, 11),
+            // 別の連結成分
+            (7, 8, 2),
+            (8, 9, 2),
+            (7, 9, 2),
+        ];
+
+        let mst = kruskal(10, &edges);
+
+        assert_eq!(mst.weight, 39 + 4);
+        assert_eq!(mst.edges, vec![0, 1, 4, 5, 7, 9, 11, 12]);
+        assert!(!mst.is_spanning_tree());
+        assert_eq!(mst.forest[4], vec![(1, 4), (2, 5), (6, 9)]);
+
+        assert_eq!(boruvka(10, &edges), mst);
+
+        // 重みの等しい (7, 9, 2) と (8, 9, 2) を入れ替える
+        assert_eq!(
+            second_minimum_spanning_forest(10, &edges),
+            Some((39 + 4, 13, 12))
+        );
+
+        // (1, 2, 8) を加えて (1, 4, 7) を取り除く
+        assert_eq!(
+            second_minimum_spanning_forest(7, &edges[..11]),
+            Some((40, 2, 4))
+        );
+
+        assert_eq!(
+            second_minimum_spanning_forest(3, &[(0, 1, 1), (1, 2, 1)]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compare_with_all_forests() {
+        let n = 5;
+
+        let graphs = vec![
+            // 重みの等しい辺が多い
+            vec![
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 0, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (4, 2, 1),
+            ],
+            // 自己ループと多重辺
+            vec![
+                (0, 0, 1),
+                (0, 1, 5),
+                (1, 0, 3),
+                (1, 2, 2),
+                (2, 1, 2),
+                (3, 4, 0),
+            ],
+            // 負の重み
+            vec![
+                (0, 1, -3),
+                (1, 2, 4),
+                (2, 3, -1),
+                (3, 0, 2),
+                (0, 2, 0),
+                (1, 3, 6),
+                (4, 4, -5),
+            ],
+            // 最小全域森がただ 1 つ
+            vec![(0, 1, 1), (1, 2, 2), (2, 3, 3), (3, 4, 4)],
+        ];
+
+        for edges in graphs {
+            let m = edges.len();
+
+            let mst = kruskal(n, &edges);
+            assert_eq!(boruvka(n, &edges), mst);
+
+            // 全ての辺集合を試して、最小全域森と同じ辺数で森になるもの
+            let mut weights = Vec::new();
+
+            for mask in 0..1_usize << m {
+                if mask.count_ones() as usize != mst.edges.len() {
+                    continue;
+                }
+
+                let mut set = crate::UnionFind::new(n);
+                let mut weight = 0;
+                let mut is_forest = true;
+
+                for (i, &(u, v, w)) in edges.iter().enumerate() {
+                    if mask >> i & 1 == 1 {
+                        is_forest &= set.union(u, v);
+                        weight += w;
+                    }
+                }
+
+                if is_forest {
+                    weights.push(weight);
+                }
+            }
+
+            weights.sort();
+
+            assert_eq!(weights[0], mst.weight);
+            assert_eq!(
+                second_minimum_spanning_forest(n, &edges).map(|(w, _, _)| w),
+                weights.get(1).copied()
+            );
+        }
+    }
+}
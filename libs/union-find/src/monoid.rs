@@ -0,0 +1,119 @@
+use crate::group::{Additive, Xor};
+use std::marker::PhantomData;
+
+///
+/// 可換モノイド
+///
+/// グループの併合順は不定なので、二項演算は可換であること
+///
+pub trait Monoid {
+    ///
+    /// モノイドの型
+    ///
+    type S: Clone;
+
+    ///
+    /// 単位元
+    ///
+    fn identity() -> Self::S;
+
+    ///
+    /// 二項演算
+    ///
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+impl<T> Monoid for Additive<T>
+where
+    T: Clone + Default + std::ops::Add<Output = T>,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::default()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        a.clone() + b.clone()
+    }
+}
+
+impl<T> Monoid for Xor<T>
+where
+    T: Clone + Default + std::ops::BitXor<Output = T>,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::default()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        a.clone() ^ b.clone()
+    }
+}
+
+///
+/// 最小値・最大値の単位元に使う上限・下限
+///
+pub trait Bounded {
+    fn min_value() -> Self;
+    fn max_value() -> Self;
+}
+
+macro_rules! impl_bounded {
+    ($($t:ty),*) => {
+        $(impl Bounded for $t {
+            fn min_value() -> Self {
+                <$t>::MIN
+            }
+
+            fn max_value() -> Self {
+                <$t>::MAX
+            }
+        })*
+    };
+}
+impl_bounded!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+///
+/// 最小値
+///
+#[derive(Debug)]
+pub struct Min<T>(PhantomData<T>);
+
+impl<T> Monoid for Min<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::max_value()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        *a.min(b)
+    }
+}
+
+///
+/// 最大値
+///
+#[derive(Debug)]
+pub struct Max<T>(PhantomData<T>);
+
+impl<T> Monoid for Max<T>
+where
+    T: Copy + Ord + Bounded,
+{
+    type S = T;
+
+    fn identity() -> Self::S {
+        T::min_value()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        *a.max(b)
+    }
+}
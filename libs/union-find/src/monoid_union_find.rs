@@ -0,0 +1,240 @@
+use crate::monoid::Monoid;
+
+///
+/// グループごとにモノイドの集約値を持つ UnionFind
+///
+/// ```
+/// use asakuchi_union_find::{Additive, MonoidUnionFind};
+///
+/// let mut set = MonoidUnionFind::<Additive<i64>>::from(vec![3, 1, 4, 1, 5]);
+///
+/// set.union(0, 2);
+/// set.union(2, 4);
+/// assert_eq!(set.aggregate(0), 12);
+///
+/// set.update(2, 10);
+/// assert_eq!(set.aggregate(4), 22);
+///
+/// set.set(2, 0);
+/// assert_eq!(set.aggregate(4), 8);
+/// ```
+///
+#[derive(Debug)]
+pub struct MonoidUnionFind<M: Monoid> {
+    parent: Vec<usize>,
+    /// 根のみ有効なグループの要素数
+    size: Vec<usize>,
+    /// グループの数
+    count: usize,
+    /// 同じグループの次の要素（循環リスト）
+    next: Vec<usize>,
+    /// 各要素の値
+    value: Vec<M::S>,
+    /// 根のみ有効なグループの集約値
+    aggregate: Vec<M::S>,
+}
+
+impl<M: Monoid> MonoidUnionFind<M> {
+    ///
+    /// 全ての要素を単位元で初期化
+    ///
+    pub fn new(n: usize) -> MonoidUnionFind<M> {
+        Self::from(vec![M::identity(); n])
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        if x != self.parent[x] {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+
+        self.parent[x]
+    }
+
+    ///
+    /// 併合した場合は true を返す
+    ///
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            return false;
+        }
+
+        // size[x] >= size[y] となるように swap
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.count -= 1;
+
+        self.next.swap(x, y);
+
+        self.aggregate[x] = M::binary_operation(&self.aggregate[x], &self.aggregate[y]);
+
+        true
+    }
+
+    pub fn equiv(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+
+        self.size[root]
+    }
+
+    ///
+    /// グループの数
+    ///
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// x が属するグループの要素の一覧
+    ///
+    /// O(グループの要素数)
+    ///
+    pub fn members(&self, x: usize) -> Vec<usize> {
+        let mut list = vec![x];
+
+        let mut current = self.next[x];
+
+        while current != x {
+            list.push(current);
+            current = self.next[current];
+        }
+
+        list
+    }
+
+    ///
+    /// x が属するグループの集約値
+    ///
+    pub fn aggregate(&mut self, x: usize) -> M::S {
+        let root = self.find(x);
+
+        self.aggregate[root].clone()
+    }
+
+    ///
+    /// x の値
+    ///
+    pub fn get(&self, x: usize) -> M::S {
+        self.value[x].clone()
+    }
+
+    ///
+    /// x の値を value との演算結果で置き換える
+    ///
+    /// O(α(n))
+    ///
+    pub fn update(&mut self, x: usize, value: M::S) {
+        let root = self.find(x);
+
+        self.value[x] = M::binary_operation(&self.value[x], &value);
+        self.aggregate[root] = M::binary_operation(&self.aggregate[root], &value);
+    }
+
+    ///
+    /// x の値を value に変更する
+    ///
+    /// 逆元を使わずにグループ全体を集約し直すので O(グループの要素数)
+    ///
+    pub fn set(&mut self, x: usize, value: M::S) {
+        let root = self.find(x);
+
+        self.value[x] = value;
+
+        self.aggregate[root] = self.members(x).into_iter().fold(M::identity(), |acc, y| {
+            M::binary_operation(&acc, &self.value[y])
+        });
+    }
+}
+
+impl<M: Monoid> From<Vec<M::S>> for MonoidUnionFind<M> {
+    fn from(value: Vec<M::S>) -> Self {
+        let n = value.len();
+
+        MonoidUnionFind {
+            parent: (0..n).collect::<Vec<_>>(),
+            size: vec![1; n],
+            count: n,
+            next: (0..n).collect::<Vec<_>>(),
+            aggregate: value.clone(),
+            value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MonoidUnionFind;
+    use crate::{Additive, Max, Min};
+
+    #[test]
+    fn test_aggregate() {
+        let mut set = MonoidUnionFind::<Min<i32>>::from(vec![5, 3, 8, 6, 7]);
+
+        assert_eq!(set.aggregate(2), 8);
+
+        set.union(2, 3);
+        set.union(4, 3);
+        assert_eq!(set.aggregate(4), 6);
+        assert_eq!(set.aggregate(1), 3);
+
+        set.update(4, 2);
+        assert_eq!(set.aggregate(2), 2);
+        assert_eq!(set.get(4), 2);
+
+        set.update(4, 9);
+        assert_eq!(set.get(4), 2);
+        assert_eq!(set.aggregate(2), 2);
+
+        // 最小値を上げる
+        set.set(4, 9);
+        assert_eq!(set.get(4), 9);
+        assert_eq!(set.aggregate(2), 6);
+
+        set.union(1, 4);
+        assert_eq!(set.aggregate(2), 3);
+
+        set.set(1, 10);
+        assert_eq!(set.aggregate(3), 6);
+
+        let mut members = set.members(3);
+        members.sort();
+        assert_eq!(members, vec![1, 2, 3, 4]);
+        assert_eq!(set.members(0), vec![0]);
+        assert_eq!(set.count(), 2);
+    }
+
+    #[test]
+    fn test_new() {
+        let mut set = MonoidUnionFind::<Max<u64>>::new(3);
+
+        assert_eq!(set.aggregate(0), 0);
+
+        set.update(0, 4);
+        set.update(2, 7);
+        set.union(0, 1);
+        assert_eq!(set.aggregate(1), 4);
+        set.union(1, 2);
+        assert_eq!(set.aggregate(0), 7);
+        assert_eq!(set.size(0), 3);
+
+        let mut set = MonoidUnionFind::<Additive<i64>>::new(2);
+
+        set.update(0, 3);
+        set.update(0, 4);
+        set.union(0, 1);
+        assert_eq!(set.aggregate(1), 7);
+    }
+}
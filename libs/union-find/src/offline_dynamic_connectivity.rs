@@ -0,0 +1,295 @@
+use crate::rollback_union_find::RollbackUnionFind;
+use std::collections::HashMap;
+
+///
+/// オフライン動的連結性判定のクエリの答え
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityAnswer {
+    /// [`OfflineDynamicConnectivity::equiv`] の答え
+    Equiv(bool),
+    /// [`OfflineDynamicConnectivity::count`] の答え
+    Count(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Event {
+    Add(usize, usize),
+    Remove(usize, usize),
+    Equiv(usize, usize),
+    Count,
+}
+
+///
+/// オフライン動的連結性判定
+///
+/// 辺の追加・削除とクエリを時系列順に登録し、最後にまとめて答える。
+/// 時間軸のセグメント木に辺の生存区間を載せ、
+/// [`RollbackUnionFind`] で DFS する
+///
+/// O(Q log Q log n)
+///
+/// ```
+/// use asakuchi_union_find::{ConnectivityAnswer, OfflineDynamicConnectivity};
+///
+/// let mut solver = OfflineDynamicConnectivity::new(3);
+///
+/// solver.add_edge(0, 1);
+/// solver.add_edge(1, 2);
+/// solver.equiv(0, 2);
+/// solver.remove_edge(0, 1);
+/// solver.equiv(0, 2);
+/// solver.count();
+///
+/// assert_eq!(
+///     solver.solve(),
+///     vec![
+///         ConnectivityAnswer::Equiv(true),
+///         ConnectivityAnswer::Equiv(false),
+///         ConnectivityAnswer::Count(2),
+///     ]
+/// );
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct OfflineDynamicConnectivity {
+    n: usize,
+    events: Vec<Event>,
+}
+
+impl OfflineDynamicConnectivity {
+    pub fn new(n: usize) -> OfflineDynamicConnectivity {
+        OfflineDynamicConnectivity {
+            n,
+            events: Vec::new(),
+        }
+    }
+
+    ///
+    /// 辺 (u, v) を追加する
+    ///
+    /// 多重辺も可
+    ///
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Add(u.min(v), u.max(v)));
+    }
+
+    ///
+    /// 辺 (u, v) を 1 本削除する
+    ///
+    /// 存在しない辺を削除しようとすると `solve` で panic する
+    ///
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Remove(u.min(v), u.max(v)));
+    }
+
+    ///
+    /// その時点で u と v が連結か
+    ///
+    pub fn equiv(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Equiv(u, v));
+    }
+
+    ///
+    /// その時点の連結成分の数
+    ///
+    pub fn count(&mut self) {
+        self.events.push(Event::Count);
+    }
+
+    ///
+    /// 登録した順にクエリの答えを返す
+    ///
+    pub fn solve(&self) -> Vec<ConnectivityAnswer> {
+        let q = self.events.len();
+
+        let mut size = 1;
+        while size < q {
+            size *= 2;
+        }
+
+        // segment[k] : 区間全体で生存している辺
+        let mut segment = vec![Vec::new(); 2 * size];
+
+        let mut add_time = HashMap::<(usize, usize), Vec<usize>>::new();
+        let mut intervals = Vec::new();
+
+        for (time, event) in self.events.iter().enumerate() {
+            match *event {
+                Event::Add(u, v) => add_time.entry((u, v)).or_default().push(time),
+                Event::Remove(u, v) => {
+                    let start = add_time
+                        .get_mut(&(u, v))
+                        .and_then(|list| list.pop())
+                        .expect("removed edge does not exist");
+
+                    intervals.push((start, time, (u, v)));
+                }
+                _ => (),
+            }
+        }
+
+        for ((u, v), list) in add_time {
+            for start in list {
+                intervals.push((start, q, (u, v)));
+            }
+        }
+
+        for (start, end, edge) in intervals {
+            let mut l = start + size;
+            let mut r = end + size;
+
+            while l < r {
+                if l & 1 == 1 {
+                    segment[l].push(edge);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    segment[r].push(edge);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        let mut set = RollbackUnionFind::new(self.n);
+        let mut answers = Vec::new();
+
+        if q > 0 {
+            self.dfs(1, size, &segment, &mut set, &mut answers);
+        }
+
+        answers
+    }
+
+    fn dfs(
+        &self,
+        k: usize,
+        size: usize,
+        segment: &Vec<Vec<(usize, usize)>>,
+        set: &mut RollbackUnionFind,
+        answers: &mut Vec<ConnectivityAnswer>,
+    ) {
+        let snapshot = set.snapshot();
+
+        for &(u, v) in segment[k].iter() {
+            set.union(u, v);
+        }
+
+        if k >= size {
+            match self.events.get(k - size) {
+                Some(&Event::Equiv(u, v)) => {
+                    answers.push(ConnectivityAnswer::Equiv(set.equiv(u, v)))
+                }
+                Some(&Event::Count) => answers.push(ConnectivityAnswer::Count(set.count())),
+                _ => (),
+            }
+        } else {
+            self.dfs(2 * k, size, segment, set, answers);
+            self.dfs(2 * k + 1, size, segment, set, answers);
+        }
+
+        set.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectivityAnswer, OfflineDynamicConnectivity};
+    use crate::UnionFind;
+
+    #[test]
+    fn test_offline_dynamic_connectivity() {
+        let mut solver = OfflineDynamicConnectivity::new(4);
+
+        solver.count();
+        solver.add_edge(0, 1);
+        solver.add_edge(1, 0);
+        solver.add_edge(2, 3);
+        solver.equiv(0, 1);
+        solver.remove_edge(0, 1);
+        solver.equiv(1, 0);
+        solver.remove_edge(1, 0);
+        solver.equiv(1, 0);
+        solver.count();
+        solver.add_edge(1, 2);
+        solver.equiv(0, 3);
+        solver.equiv(1, 3);
+
+        use ConnectivityAnswer::*;
+
+        assert_eq!(
+            solver.solve(),
+            vec![
+                Count(4),
+                Equiv(true),
+                Equiv(true),
+                Equiv(false),
+                Count(3),
+                Equiv(false),
+                Equiv(true),
+            ]
+        );
+
+        assert!(OfflineDynamicConnectivity::new(2).solve().is_empty());
+    }
+
+    #[test]
+    fn test_compare_with_rebuild() {
+        // 操作ごとに UnionFind を作り直す愚直解と、全ての頂点対・連結成分数を比較
+        let n = 5;
+        let mut solver = OfflineDynamicConnectivity::new(n);
+        let mut edges = Vec::new();
+        let mut expected = Vec::new();
+
+        // (追加なら true, u, v)
+        let operations = [
+            (true, 0, 1),
+            (true, 1, 2),
+            (true, 0, 2),
+            (true, 3, 3),
+            (false, 1, 0),
+            (true, 2, 4),
+            (true, 4, 2),
+            (false, 2, 1),
+            (false, 4, 2),
+            (true, 3, 4),
+            (false, 0, 2),
+            (false, 3, 3),
+            (true, 1, 3),
+            (false, 2, 4),
+        ];
+
+        for &(add, u, v) in operations.iter() {
+            if add {
+                solver.add_edge(u, v);
+                edges.push((u, v));
+            } else {
+                let index = edges
+                    .iter()
+                    .position(|&e| e == (u, v) || e == (v, u))
+                    .unwrap();
+                edges.swap_remove(index);
+                solver.remove_edge(u, v);
+            }
+
+            let mut set = UnionFind::new(n);
+            for &(a, b) in edges.iter() {
+                set.union(a, b);
+            }
+
+            solver.count();
+            expected.push(ConnectivityAnswer::Count(set.count()));
+
+            for u in 0..n {
+                for v in 0..n {
+                    solver.equiv(u, v);
+                    expected.push(ConnectivityAnswer::Equiv(set.equiv(u, v)));
+                }
+            }
+        }
+
+        assert_eq!(solver.solve(), expected);
+    }
+}
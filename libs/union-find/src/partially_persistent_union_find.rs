@@ -0,0 +1,229 @@
+///
+/// 部分永続 UnionFind
+///
+/// 時刻 t は t 回目の union を行った直後を表す（初期状態は時刻 0）
+///
+/// 経路圧縮を行わず、サイズによる併合のみを行う。
+/// 各クエリは O(log n)
+///
+/// ```
+/// use asakuchi_union_find::PartiallyPersistentUnionFind;
+///
+/// let mut set = PartiallyPersistentUnionFind::new(4);
+///
+/// set.union(0, 1); // 時刻 1
+/// set.union(2, 3); // 時刻 2
+/// set.union(1, 3); // 時刻 3
+///
+/// assert!(!set.equiv_at(0, 2, 2));
+/// assert!(set.equiv_at(0, 2, 3));
+/// assert_eq!(set.first_connected_time(0, 3), Some(3));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PartiallyPersistentUnionFind {
+    parent: Vec<usize>,
+    /// 根でなくなった時刻
+    time: Vec<usize>,
+    /// 根だった間のグループの要素数の履歴 (時刻, 要素数)
+    size_history: Vec<Vec<(usize, usize)>>,
+    /// 現在の時刻
+    now: usize,
+}
+
+impl PartiallyPersistentUnionFind {
+    const INF: usize = usize::MAX;
+
+    pub fn new(n: usize) -> PartiallyPersistentUnionFind {
+        PartiallyPersistentUnionFind {
+            parent: (0..n).collect::<Vec<_>>(),
+            time: vec![Self::INF; n],
+            size_history: vec![vec![(0, 1)]; n],
+            now: 0,
+        }
+    }
+
+    ///
+    /// 現在の時刻（union を行った回数）
+    ///
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    ///
+    /// 時刻を 1 進めて併合する
+    ///
+    /// 併合した場合は true を返す
+    ///
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        self.now += 1;
+
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            return false;
+        }
+
+        let size_x = self.size(x);
+        let size_y = self.size(y);
+
+        // size[x] >= size[y] となるように swap
+        if size_x < size_y {
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        self.parent[y] = x;
+        self.time[y] = self.now;
+        self.size_history[x].push((self.now, size_x + size_y));
+
+        true
+    }
+
+    ///
+    /// 現在の根
+    ///
+    pub fn find(&self, x: usize) -> usize {
+        self.find_at(x, self.now)
+    }
+
+    ///
+    /// 時刻 t における根
+    ///
+    pub fn find_at(&self, x: usize, t: usize) -> usize {
+        let mut x = x;
+
+        while self.time[x] <= t {
+            x = self.parent[x];
+        }
+
+        x
+    }
+
+    pub fn equiv(&self, x: usize, y: usize) -> bool {
+        self.equiv_at(x, y, self.now)
+    }
+
+    ///
+    /// 時刻 t に同じグループか
+    ///
+    pub fn equiv_at(&self, x: usize, y: usize, t: usize) -> bool {
+        self.find_at(x, t) == self.find_at(y, t)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&self, x: usize) -> usize {
+        self.size_at(x, self.now)
+    }
+
+    ///
+    /// 時刻 t に x が属していたグループの要素数
+    ///
+    pub fn size_at(&self, x: usize, t: usize) -> usize {
+        let root = self.find_at(x, t);
+
+        let history = &self.size_history[root];
+
+        // 時刻 t 以前の最後の記録
+        let index = history.partition_point(|&(time, _)| time <= t);
+
+        history[index - 1].1
+    }
+
+    ///
+    /// x と y が初めて同じグループになった時刻
+    ///
+    /// 現在も別のグループなら None
+    ///
+    pub fn first_connected_time(&self, x: usize, y: usize) -> Option<usize> {
+        let mut x = x;
+        let mut y = y;
+        let mut t = 0;
+
+        // 根でなくなる時刻が早い方から辿る
+        while x != y {
+            if self.time[x] < self.time[y] {
+                t = self.time[x];
+                x = self.parent[x];
+            } else {
+                t = self.time[y];
+                y = self.parent[y];
+            }
+
+            if t == Self::INF {
+                return None;
+            }
+        }
+
+        Some(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartiallyPersistentUnionFind;
+
+    #[test]
+    fn test_partially_persistent() {
+        let mut set = PartiallyPersistentUnionFind::new(6);
+
+        set.union(0, 1); // 1
+        set.union(2, 3); // 2
+        set.union(0, 1); // 3
+        set.union(4, 2); // 4
+        set.union(3, 1); // 5
+
+        assert_eq!(set.now(), 5);
+
+        assert!(!set.equiv_at(0, 1, 0));
+        assert!(set.equiv_at(0, 1, 1));
+        assert!(!set.equiv_at(0, 4, 4));
+        assert!(set.equiv_at(0, 4, 5));
+        assert!(!set.equiv(0, 5));
+
+        assert_eq!(set.size_at(2, 1), 1);
+        assert_eq!(set.size_at(2, 2), 2);
+        assert_eq!(set.size_at(3, 4), 3);
+        assert_eq!(set.size_at(0, 4), 2);
+        assert_eq!(set.size_at(0, 5), 5);
+        assert_eq!(set.size(5), 1);
+
+        assert_eq!(set.first_connected_time(0, 0), Some(0));
+        assert_eq!(set.first_connected_time(0, 1), Some(1));
+        assert_eq!(set.first_connected_time(2, 4), Some(4));
+        assert_eq!(set.first_connected_time(4, 1), Some(5));
+        assert_eq!(set.first_connected_time(0, 5), None);
+    }
+
+    #[test]
+    fn test_first_connected_time() {
+        // 愚直に各時刻を調べた結果と比較
+        let n = 8;
+        let edges = [
+            (0, 1),
+            (2, 3),
+            (4, 5),
+            (1, 3),
+            (6, 7),
+            (5, 7),
+            (0, 2),
+            (3, 6),
+        ];
+
+        let mut set = PartiallyPersistentUnionFind::new(n);
+
+        for &(x, y) in edges.iter() {
+            set.union(x, y);
+        }
+
+        for x in 0..n {
+            for y in 0..n {
+                let expected = (0..=set.now()).find(|&t| set.equiv_at(x, y, t));
+
+                assert_eq!(set.first_connected_time(x, y), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use asakuchi_persistent_array::PersistentArray;
+use std::rc::Rc;
+
+///
+/// 完全永続 UnionFind
+///
+/// union は新しいバージョンを返し、元のバージョンは変更されない。
+/// どのバージョンからでも分岐できる
+///
+/// 経路圧縮を行わず、サイズによる併合のみを行う
+///
+/// ```
+/// use asakuchi_union_find::PersistentUnionFind;
+///
+/// let v_0 = PersistentUnionFind::new(3);
+/// let v_1 = v_0.union(0, 1);
+/// let v_2 = v_0.union(1, 2);
+///
+/// assert!(!v_0.equiv(0, 1));
+/// assert!(v_1.equiv(0, 1));
+/// assert!(!v_2.equiv(0, 1));
+/// assert!(v_2.equiv(1, 2));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct PersistentUnionFind {
+    n: usize,
+    /// 親 + 1 (根は 0)
+    parent: Rc<PersistentArray>,
+    /// 根のみ有効なグループの要素数 - 1
+    size: Rc<PersistentArray>,
+}
+
+impl PersistentUnionFind {
+    pub fn new(n: usize) -> PersistentUnionFind {
+        PersistentUnionFind {
+            n,
+            parent: PersistentArray::new(),
+            size: PersistentArray::new(),
+        }
+    }
+
+    ///
+    /// O(log n) 回の永続配列へのアクセス
+    ///
+    pub fn find(&self, x: usize) -> usize {
+        assert!(x < self.n, "index out of bounds");
+
+        let mut x = x;
+
+        loop {
+            match self.parent.get(x) {
+                0 => return x,
+                p => x = p - 1,
+            }
+        }
+    }
+
+    ///
+    /// x と y を併合した新しいバージョンを返す
+    ///
+    /// 既に同じグループの場合は同じ内容のバージョンを返す
+    ///
+    pub fn union(&self, x: usize, y: usize) -> PersistentUnionFind {
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            return self.clone();
+        }
+
+        // size[x] >= size[y] となるように swap
+        if self.size.get(x) < self.size.get(y) {
+            std::mem::swap(&mut x, &mut y);
+        }
+
+        let size = self.size.get(x) + self.size.get(y) + 1;
+
+        PersistentUnionFind {
+            n: self.n,
+            parent: self.parent.set(y, x + 1),
+            size: self.size.set(x, size),
+        }
+    }
+
+    pub fn equiv(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&self, x: usize) -> usize {
+        self.size.get(self.find(x)) + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PersistentUnionFind;
+
+    #[test]
+    fn test_persistent() {
+        let v_0 = PersistentUnionFind::new(5);
+        let v_1 = v_0.union(0, 1);
+        let v_2 = v_1.union(2, 3);
+        let v_3 = v_2.union(1, 3);
+
+        // v_1 から分岐
+        let w_2 = v_1.union(1, 4);
+        let w_3 = w_2.union(4, 0);
+
+        assert!(!v_0.equiv(0, 1));
+        assert!(v_1.equiv(0, 1));
+        assert!(!v_2.equiv(0, 3));
+        assert!(v_3.equiv(0, 3));
+
+        assert!(!w_2.equiv(2, 3));
+        assert!(w_2.equiv(0, 4));
+        assert!(!v_3.equiv(0, 4));
+
+        assert_eq!(v_0.size(0), 1);
+        assert_eq!(v_1.size(1), 2);
+        assert_eq!(v_3.size(2), 4);
+        assert_eq!(w_2.size(4), 3);
+        assert_eq!(w_3.size(4), 3);
+    }
+}
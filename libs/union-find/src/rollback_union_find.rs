@@ -0,0 +1,251 @@
+///
+/// 巻き戻し可能な UnionFind
+///
+/// 経路圧縮を行わず、サイズによる併合のみを行う。
+/// `find` は O(log n)
+///
+/// ```
+/// use asakuchi_union_find::RollbackUnionFind;
+///
+/// let mut set = RollbackUnionFind::new(3);
+///
+/// let snapshot = set.snapshot();
+///
+/// set.union(0, 1);
+/// set.union(1, 2);
+/// assert!(set.equiv(0, 2));
+///
+/// set.undo();
+/// assert!(set.equiv(0, 1));
+/// assert!(!set.equiv(0, 2));
+///
+/// set.rollback(snapshot);
+/// assert!(!set.equiv(0, 1));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    /// グループの数
+    count: usize,
+    /// 親からの重み
+    diff_weight: Vec<isize>,
+    /// union の履歴
+    /// 併合した場合は (子になった根, 親になった根)
+    history: Vec<Option<(usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(n: usize) -> RollbackUnionFind {
+        RollbackUnionFind {
+            parent: (0..n).collect::<Vec<_>>(),
+            size: vec![1; n],
+            count: n,
+            diff_weight: vec![0; n],
+            history: Vec::new(),
+        }
+    }
+
+    pub fn find(&self, x: usize) -> usize {
+        let mut x = x;
+
+        while x != self.parent[x] {
+            x = self.parent[x];
+        }
+
+        x
+    }
+
+    ///
+    /// 併合した場合は true を返す
+    ///
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        if self.equiv(x, y) {
+            self.history.push(None);
+            return false;
+        }
+
+        self.union_weight(x, y, 0)
+    }
+
+    ///
+    /// `diff(x, y) = w` となるように併合する
+    ///
+    /// 既に同じグループの場合は何もせず（履歴には残る）、
+    /// 既存の制約と矛盾しなければ true、矛盾すれば false を返す
+    ///
+    pub fn union_weight(&mut self, x: usize, y: usize, w: isize) -> bool {
+        let consistent = self.diff(x, y) == w;
+        let mut w = w + self.weight(x) - self.weight(y);
+
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+
+        if x == y {
+            self.history.push(None);
+            return consistent;
+        }
+
+        // size[x] >= size[y] となるように swap
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+            w = -w;
+        }
+
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        self.count -= 1;
+        self.diff_weight[y] = w;
+
+        self.history.push(Some((y, x)));
+
+        true
+    }
+
+    pub fn equiv(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    ///
+    /// 根からの重み
+    ///
+    pub fn weight(&self, x: usize) -> isize {
+        let mut x = x;
+        let mut weight = 0;
+
+        while x != self.parent[x] {
+            weight += self.diff_weight[x];
+            x = self.parent[x];
+        }
+
+        weight
+    }
+
+    ///
+    /// ノード間の距離を返す
+    ///
+    pub fn diff(&self, x: usize, y: usize) -> isize {
+        self.weight(y) - self.weight(x)
+    }
+
+    ///
+    /// x が属するグループの要素数
+    ///
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    ///
+    /// グループの数
+    ///
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    ///
+    /// 現在の状態を表す値を返す
+    ///
+    /// [`rollback`](Self::rollback) に渡すとこの状態に戻せる
+    ///
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    ///
+    /// 直前の union を取り消す
+    ///
+    /// 取り消す union がなければ false を返す
+    ///
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Some((y, x))) => {
+                self.parent[y] = y;
+                self.size[x] -= self.size[y];
+                self.count += 1;
+                self.diff_weight[y] = 0;
+                true
+            }
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    ///
+    /// [`snapshot`](Self::snapshot) を取った時点の状態に戻す
+    ///
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(
+            snapshot <= self.history.len(),
+            "snapshot is newer than current state"
+        );
+
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollbackUnionFind;
+
+    #[test]
+    fn test_rollback() {
+        let mut set = RollbackUnionFind::new(5);
+
+        assert!(set.union(0, 1));
+        let snapshot = set.snapshot();
+
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.equiv(0, 3));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.count(), 2);
+
+        assert!(set.undo());
+        assert!(set.equiv(0, 3));
+
+        assert!(set.undo());
+        assert!(!set.equiv(0, 3));
+        assert!(set.equiv(2, 3));
+
+        set.rollback(snapshot);
+        assert!(set.equiv(0, 1));
+        assert!(!set.equiv(2, 3));
+        assert_eq!(set.size(2), 1);
+        assert_eq!(set.count(), 4);
+
+        set.rollback(0);
+        assert!(!set.equiv(0, 1));
+        assert!(!set.undo());
+    }
+
+    #[test]
+    fn test_rollback_weight() {
+        let mut set = RollbackUnionFind::new(4);
+
+        set.union_weight(0, 1, 3);
+        set.union_weight(2, 3, -2);
+        let snapshot = set.snapshot();
+
+        set.union_weight(1, 2, 5);
+
+        assert_eq!(set.diff(0, 1), 3);
+        assert_eq!(set.diff(0, 2), 8);
+        assert_eq!(set.diff(0, 3), 6);
+        assert_eq!(set.diff(3, 0), -6);
+
+        assert!(set.union_weight(3, 1, -3));
+        assert!(!set.union_weight(3, 1, 3));
+        assert!(!set.union(0, 3));
+
+        set.rollback(snapshot);
+
+        assert!(!set.equiv(0, 3));
+        assert_eq!(set.diff(0, 1), 3);
+        assert_eq!(set.diff(2, 3), -2);
+    }
+}
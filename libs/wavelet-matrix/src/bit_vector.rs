@@ -0,0 +1,77 @@
+///
+/// 完備辞書
+///
+/// rank を O(1) で求める
+///
+#[derive(Debug, Clone)]
+pub(crate) struct BitVector {
+    blocks: Vec<u64>,
+    /// count[i] : blocks[..i] の 1 の個数
+    count: Vec<usize>,
+}
+
+impl BitVector {
+    pub(crate) fn new(bits: &[bool]) -> BitVector {
+        let mut blocks = vec![0_u64; bits.len() / 64 + 1];
+
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                blocks[i / 64] |= 1 << (i % 64);
+            }
+        }
+
+        let mut count = Vec::with_capacity(blocks.len() + 1);
+        count.push(0);
+
+        for block in blocks.iter() {
+            count.push(count.last().unwrap() + block.count_ones() as usize);
+        }
+
+        BitVector { blocks, count }
+    }
+
+    pub(crate) fn get(&self, i: usize) -> bool {
+        self.blocks[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    ///
+    /// [0, i) の 1 の個数
+    ///
+    pub(crate) fn rank1(&self, i: usize) -> usize {
+        let mask = (1_u64 << (i % 64)) - 1;
+
+        self.count[i / 64] + (self.blocks[i / 64] & mask).count_ones() as usize
+    }
+
+    ///
+    /// [0, i) の 0 の個数
+    ///
+    pub(crate) fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitVector;
+
+    #[test]
+    fn test_bit_vector() {
+        let bits = (0..200)
+            .map(|i| i % 3 == 0 || i % 7 == 0)
+            .collect::<Vec<_>>();
+
+        let vector = BitVector::new(&bits);
+
+        for i in 0..=bits.len() {
+            let expected = bits[..i].iter().filter(|&&b| b).count();
+
+            assert_eq!(vector.rank1(i), expected);
+            assert_eq!(vector.rank0(i), i - expected);
+        }
+
+        for (i, &b) in bits.iter().enumerate() {
+            assert_eq!(vector.get(i), b);
+        }
+    }
+}
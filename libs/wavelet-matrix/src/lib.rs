@@ -0,0 +1,422 @@
+//!
+//! Wavelet Matrix
+//!
+//! 静的な列に対する区間 k 番目・区間頻度などをオンラインで求める
+//!
+//! 値の種類数を σ として、各クエリ O(log σ)
+//!
+
+mod bit_vector;
+
+use asakuchi_coordinate_compression::CoordinateCompression;
+use bit_vector::BitVector;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::{Bound, RangeBounds};
+
+///
+/// Wavelet Matrix
+///
+/// 値は座標圧縮して持つので、値の範囲は問わない
+///
+/// ```
+/// use asakuchi_wavelet_matrix::WaveletMatrix;
+///
+/// let matrix = WaveletMatrix::new(vec![5, 4, 5, 5, 2, 1, 5, 6, 1, 3]);
+///
+/// assert_eq!(matrix.kth_smallest(1..6, 2), Some(4));
+/// assert_eq!(matrix.range_freq(.., 2..5), 3);
+/// assert_eq!(matrix.top_k(..8, 2), vec![(5, 4), (1, 1)]);
+/// assert_eq!(matrix.prev_value(4..9, &5), Some(2));
+/// assert_eq!(matrix.next_value(4..9, &3), Some(5));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct WaveletMatrix<T: Ord + Clone> {
+    len: usize,
+    /// 値の種類数 σ に対して 2^bits >= σ
+    bits: usize,
+    /// levels[d] : 上から d 番目のビット
+    levels: Vec<BitVector>,
+    /// zeros[d] : levels[d] の 0 の個数
+    zeros: Vec<usize>,
+    compression: CoordinateCompression<T>,
+}
+
+impl<T: Ord + Clone> WaveletMatrix<T> {
+    ///
+    /// O(n log σ)
+    ///
+    pub fn new(list: Vec<T>) -> WaveletMatrix<T> {
+        let len = list.len();
+
+        let compression = CoordinateCompression::new(list.iter().cloned());
+
+        let mut current = list
+            .iter()
+            .map(|x| compression.index_of(x))
+            .collect::<Vec<_>>();
+
+        let mut bits = 0;
+        while (1 << bits) < compression.len() {
+            bits += 1;
+        }
+
+        let mut levels = Vec::with_capacity(bits);
+        let mut zeros = Vec::with_capacity(bits);
+
+        for d in 0..bits {
+            let bit = bits - 1 - d;
+
+            let flags = current
+                .iter()
+                .map(|&x| x >> bit & 1 == 1)
+                .collect::<Vec<_>>();
+
+            levels.push(BitVector::new(&flags));
+            zeros.push(flags.iter().filter(|&&b| !b).count());
+
+            // 0 を前に、1 を後ろに安定に並べ替える
+            let (mut next, ones): (Vec<_>, Vec<_>) =
+                current.into_iter().partition(|&x| x >> bit & 1 == 0);
+            next.extend(ones);
+
+            current = next;
+        }
+
+        WaveletMatrix {
+            len,
+            bits,
+            levels,
+            zeros,
+            compression,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// i 番目の値
+    ///
+    pub fn access(&self, i: usize) -> T {
+        assert!(i < self.len, "index out of bounds");
+
+        let mut i = i;
+        let mut value = 0;
+
+        for d in 0..self.bits {
+            let level = &self.levels[d];
+
+            if level.get(i) {
+                value |= 1 << (self.bits - 1 - d);
+                i = self.zeros[d] + level.rank1(i);
+            } else {
+                i = level.rank0(i);
+            }
+        }
+
+        self.compression.value_at(value).clone()
+    }
+
+    ///
+    /// 区間 `range` の中で小さい方から k 番目（0-indexed）の値
+    ///
+    pub fn kth_smallest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Option<T> {
+        let (mut l, mut r) = self.to_half_open(range);
+
+        if k >= r - l {
+            return None;
+        }
+
+        let mut k = k;
+        let mut value = 0;
+
+        for d in 0..self.bits {
+            let level = &self.levels[d];
+
+            let (zero_l, zero_r) = (level.rank0(l), level.rank0(r));
+
+            if k < zero_r - zero_l {
+                l = zero_l;
+                r = zero_r;
+            } else {
+                k -= zero_r - zero_l;
+                value |= 1 << (self.bits - 1 - d);
+                l = self.zeros[d] + (l - zero_l);
+                r = self.zeros[d] + (r - zero_r);
+            }
+        }
+
+        Some(self.compression.value_at(value).clone())
+    }
+
+    ///
+    /// 区間 `range` の中で大きい方から k 番目（0-indexed）の値
+    ///
+    pub fn kth_largest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Option<T> {
+        let (l, r) = self.to_half_open(range);
+
+        if k >= r - l {
+            return None;
+        }
+
+        self.kth_smallest(l..r, r - l - 1 - k)
+    }
+
+    ///
+    /// 区間 `range` の中で値が `value_range` に含まれる要素の個数
+    ///
+    pub fn range_freq<R, Q>(&self, range: R, value_range: Q) -> usize
+    where
+        R: RangeBounds<usize>,
+        Q: RangeBounds<T>,
+    {
+        let (l, r) = self.to_half_open(range);
+
+        let lower = match value_range.start_bound() {
+            Bound::Included(x) => self.compression.lower_bound(x),
+            Bound::Excluded(x) => self.compression.upper_bound(x),
+            Bound::Unbounded => 0,
+        };
+
+        let upper = match value_range.end_bound() {
+            Bound::Included(x) => self.compression.upper_bound(x),
+            Bound::Excluded(x) => self.compression.lower_bound(x),
+            Bound::Unbounded => self.compression.len(),
+        };
+
+        if lower >= upper {
+            return 0;
+        }
+
+        self.count_less(l, r, upper) - self.count_less(l, r, lower)
+    }
+
+    ///
+    /// 区間 `range` の中の x の個数
+    ///
+    pub fn count<R: RangeBounds<usize>>(&self, range: R, x: &T) -> usize {
+        self.range_freq(range, x..=x)
+    }
+
+    ///
+    /// 区間 `range` の中で出現回数の多い順に k 個の (値, 出現回数)
+    ///
+    /// 出現回数が同じなら値の小さい順
+    ///
+    /// O(k log σ log k)
+    ///
+    pub fn top_k<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Vec<(T, usize)> {
+        let (l, r) = self.to_half_open(range);
+
+        let mut result = Vec::with_capacity(k);
+
+        // (個数, 部分木の最小の値, l, r, 深さ)
+        let mut heap = BinaryHeap::new();
+
+        if l < r {
+            heap.push((r - l, Reverse(0), l, r, 0));
+        }
+
+        while let Some((width, Reverse(value), l, r, d)) = heap.pop() {
+            if result.len() >= k {
+                break;
+            }
+
+            if d == self.bits {
+                result.push((self.compression.value_at(value).clone(), width));
+                continue;
+            }
+
+            let level = &self.levels[d];
+
+            let (zero_l, zero_r) = (level.rank0(l), level.rank0(r));
+
+            if zero_l < zero_r {
+                heap.push((zero_r - zero_l, Reverse(value), zero_l, zero_r, d + 1));
+            }
+
+            let (one_l, one_r) = (self.zeros[d] + (l - zero_l), self.zeros[d] + (r - zero_r));
+
+            if one_l < one_r {
+                let value = value | 1 << (self.bits - 1 - d);
+
+                heap.push((one_r - one_l, Reverse(value), one_l, one_r, d + 1));
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// 区間 `range` の中で x 未満の最大の値
+    ///
+    pub fn prev_value<R: RangeBounds<usize>>(&self, range: R, x: &T) -> Option<T> {
+        let (l, r) = self.to_half_open(range);
+
+        match self.count_less(l, r, self.compression.lower_bound(x)) {
+            0 => None,
+            c => self.kth_smallest(l..r, c - 1),
+        }
+    }
+
+    ///
+    /// 区間 `range` の中で x 以上の最小の値
+    ///
+    pub fn next_value<R: RangeBounds<usize>>(&self, range: R, x: &T) -> Option<T> {
+        let (l, r) = self.to_half_open(range);
+
+        self.kth_smallest(l..r, self.count_less(l, r, self.compression.lower_bound(x)))
+    }
+
+    ///
+    /// [l, r) の中で圧縮後の値が upper 未満の要素の個数
+    ///
+    fn count_less(&self, l: usize, r: usize, upper: usize) -> usize {
+        if upper >= 1 << self.bits {
+            return r - l;
+        }
+
+        let (mut l, mut r) = (l, r);
+        let mut count = 0;
+
+        for d in 0..self.bits {
+            let level = &self.levels[d];
+
+            let (zero_l, zero_r) = (level.rank0(l), level.rank0(r));
+
+            if upper >> (self.bits - 1 - d) & 1 == 1 {
+                count += zero_r - zero_l;
+                l = self.zeros[d] + (l - zero_l);
+                r = self.zeros[d] + (r - zero_r);
+            } else {
+                l = zero_l;
+                r = zero_r;
+            }
+        }
+
+        count
+    }
+
+    fn to_half_open<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let l = match range.start_bound() {
+            Bound::Included(&l) => l,
+            Bound::Excluded(&l) => l + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let r = match range.end_bound() {
+            Bound::Included(&r) => r + 1,
+            Bound::Excluded(&r) => r,
+            Bound::Unbounded => self.len,
+        };
+
+        assert!(l <= r && r <= self.len, "range out of bounds");
+
+        (l, r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WaveletMatrix;
+
+    #[test]
+    fn test_all_ranges() {
+        let lists: Vec<Vec<i64>> = vec![
+            vec![],
+            vec![-50],
+            vec![4; 7],
+            vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9],
+            vec![-7, 0, i64::MAX, -7, 12, i64::MIN, 0, 3, -100, 12, 12, 5],
+        ];
+
+        // 列に含まれる値とその前後
+        let keys = [
+            i64::MIN,
+            -101,
+            -100,
+            -7,
+            -1,
+            0,
+            1,
+            4,
+            5,
+            9,
+            10,
+            12,
+            13,
+            i64::MAX,
+        ];
+
+        for list in lists {
+            let n = list.len();
+            let matrix = WaveletMatrix::new(list.clone());
+
+            assert_eq!(matrix.len(), n);
+
+            for (i, &x) in list.iter().enumerate() {
+                assert_eq!(matrix.access(i), x);
+            }
+
+            for l in 0..=n {
+                for r in l..=n {
+                    let mut sorted = list[l..r].to_vec();
+                    sorted.sort();
+
+                    for k in 0..=sorted.len() {
+                        assert_eq!(matrix.kth_smallest(l..r, k), sorted.get(k).copied());
+                        assert_eq!(
+                            matrix.kth_largest(l..r, k),
+                            sorted.iter().rev().nth(k).copied()
+                        );
+                    }
+
+                    for &a in keys.iter() {
+                        for &b in keys.iter() {
+                            assert_eq!(
+                                matrix.range_freq(l..r, a..b),
+                                sorted.iter().filter(|&&x| a <= x && x < b).count()
+                            );
+                        }
+
+                        assert_eq!(
+                            matrix.count(l..r, &a),
+                            sorted.iter().filter(|&&x| x == a).count()
+                        );
+                        assert_eq!(
+                            matrix.prev_value(l..r, &a),
+                            sorted.iter().rev().find(|&&x| x < a).copied()
+                        );
+                        assert_eq!(
+                            matrix.next_value(l..r, &a),
+                            sorted.iter().find(|&&x| x >= a).copied()
+                        );
+                    }
+
+                    // 出現回数の降順、値の昇順
+                    let mut frequency = Vec::<(i64, usize)>::new();
+                    for &x in sorted.iter() {
+                        match frequency.last_mut() {
+                            Some((y, c)) if *y == x => *c += 1,
+                            _ => frequency.push((x, 1)),
+                        }
+                    }
+                    frequency.sort_by_key(|&(x, c)| (std::cmp::Reverse(c), x));
+
+                    for k in 0..=frequency.len() + 1 {
+                        let expected = frequency.iter().take(k).copied().collect::<Vec<_>>();
+
+                        assert_eq!(matrix.top_k(l..r, k), expected);
+                    }
+                }
+            }
+        }
+    }
+}